@@ -0,0 +1,102 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The answer to a part of a puzzle, whatever the type its solver returns.
+///
+/// Integers are kept in a single form whatever their width and sign, so that `Answer::from(5u32)`
+/// equals `Answer::from(5i64)` and `"5".parse::<Answer>()`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// Any integer that fits in an `i128`.
+    Int(i128),
+    /// An unsigned integer too large for [`Answer::Int`].
+    BigUint(u128),
+    Text(String),
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Int(n.into())
+            }
+        }
+    )*};
+}
+
+from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigUint(n),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        (n as u128).into()
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.into())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigUint(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Anything that reads as an integer becomes one, anything else is kept as text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        let s = s.trim();
+        Ok(match (s.parse::<i128>(), s.parse::<u128>()) {
+            (Ok(n), _) => Answer::Int(n),
+            (_, Ok(n)) => Answer::BigUint(n),
+            _ => Answer::Text(s.into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answer;
+
+    #[test]
+    fn equality() {
+        assert_eq!(Answer::from(5u32), Answer::from(5i64));
+        assert_eq!(Answer::from(5usize), "5".parse().unwrap());
+        assert_eq!(Answer::from(-3isize), " -3\n".parse().unwrap());
+        assert_ne!(Answer::from(5u8), Answer::from("5"));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            u128::MAX.to_string().parse().unwrap()
+        );
+        assert_eq!("ABC".parse(), Ok(Answer::from("ABC")));
+        assert_eq!(Answer::from(i64::MIN).to_string(), i64::MIN.to_string());
+    }
+}
@@ -0,0 +1,256 @@
+use std::path::{Path, PathBuf};
+
+use crate::{input_from_file, Answer, Error, Result};
+
+/// The answer expected for one part of a day on a given input, as recorded in `answers.toml`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: PathBuf,
+    pub expected: Answer,
+}
+
+/// The result of checking a [`Record`] against the current solution.
+#[derive(Debug)]
+pub enum Outcome {
+    Correct,
+    Wrong(Answer),
+    Failed(Error),
+}
+
+impl Record {
+    /// Whether the input is one of the examples of the puzzle description, such as `inputs/08-example2.txt`.
+    pub fn is_example(&self) -> bool {
+        is_example(&self.input)
+    }
+
+    /// Names the record in reports, as `Day 08 part 2 on inputs/08-example3.txt`.
+    pub fn name(&self) -> String {
+        format!(
+            "Day {:02} part {} on {}",
+            self.day,
+            self.part,
+            self.input.display()
+        )
+    }
+}
+
+fn is_example(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.contains("-example") && name.ends_with(".txt"))
+}
+
+/// The example inputs in `dir` that no record checks, so that a new example is not forgotten in the manifest.
+/// Empty files, as `aoc new` leaves them until the example is pasted in, do not count.
+pub fn unlisted_examples(records: &[Record], dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let mut unlisted = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if is_example(&path)
+            && entry.metadata()?.len() > 0
+            && !records.iter().any(|r| r.input == path)
+        {
+            unlisted.push(path);
+        }
+    }
+    unlisted.sort();
+    Ok(unlisted)
+}
+
+pub fn load(path: impl AsRef<Path>) -> Result<Vec<Record>> {
+    parse(&input_from_file(path)?)
+}
+
+/// Parses a manifest made of `[[answer]]` tables, each with a `day`, a `part`, an `input` file
+/// and the `expected` answer:
+/// ```toml
+/// [[answer]]
+/// day = 1
+/// part = 1
+/// input = "inputs/01-example1.txt"
+/// expected = 142
+/// ```
+/// This is the only subset of TOML that is supported, along with comments and blank lines.
+pub fn parse(manifest: &str) -> Result<Vec<Record>> {
+    let mut records = vec![];
+    let mut current: Option<(usize, [Option<String>; 4])> = None;
+
+    for (idx, line) in manifest.lines().enumerate() {
+        let content = strip_comment(line).trim();
+        if content.is_empty() {
+            continue;
+        }
+        if content == "[[answer]]" {
+            if let Some(table) = current.take() {
+                records.push(record(table)?);
+            }
+            current = Some((idx, Default::default()));
+            continue;
+        }
+
+        let Some((_, fields)) = current.as_mut() else {
+            return Err(Error::at(idx, line, content, "expected '[[answer]]'"));
+        };
+        let (key, value) = content
+            .split_once('=')
+            .ok_or_else(|| Error::at(idx, line, content, "expected '<key> = <value>'"))?;
+        let slot = match key.trim() {
+            "day" => &mut fields[0],
+            "part" => &mut fields[1],
+            "input" => &mut fields[2],
+            "expected" => &mut fields[3],
+            _ => return Err(Error::at(idx, line, key.trim(), "unknown key")),
+        };
+        if slot.is_some() {
+            return Err(Error::at(idx, line, key.trim(), "duplicate key"));
+        }
+        *slot = Some(parse_value(value.trim()).ok_or_else(|| {
+            Error::at(idx, line, value.trim(), "expected an integer or a string")
+        })?);
+    }
+
+    if let Some(table) = current {
+        records.push(record(table)?);
+    }
+    Ok(records)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_value(value: &str) -> Option<String> {
+    match value.strip_prefix('"') {
+        Some(s) => s
+            .strip_suffix('"')
+            .filter(|s| !s.contains('"'))
+            .map(String::from),
+        None => {
+            let value = value.replace('_', "");
+            value.parse::<i128>().ok().map(|_| value)
+        }
+    }
+}
+
+fn record((idx, [day, part, input, expected]): (usize, [Option<String>; 4])) -> Result<Record> {
+    let missing = |key: &str| Error::parse(idx + 1, 1, format!("missing '{key}'"));
+    let number = |value: String, key: &str| {
+        value
+            .parse()
+            .map_err(|_| Error::parse(idx + 1, 1, format!("invalid '{key}'")))
+    };
+
+    Ok(Record {
+        day: number(day.ok_or_else(|| missing("day"))?, "day")?,
+        part: number(part.ok_or_else(|| missing("part"))?, "part")?,
+        input: input.ok_or_else(|| missing("input"))?.into(),
+        expected: match expected.ok_or_else(|| missing("expected"))?.parse() {
+            Ok(answer) => answer,
+            Err(e) => match e {},
+        },
+    })
+}
+
+/// Solves the part of the day of the record on its input, and compares the answer with the expected one.
+pub fn check(record: &Record) -> Outcome {
+    let Some(day) = crate::day(record.day) else {
+        return Outcome::Failed(Error::Invalid(format!(
+            "No solution for day {}",
+            record.day
+        )));
+    };
+    let answer = input_from_file(&record.input)
+        .and_then(|input| day.parse(&input))
+        .and_then(|input| day.solve(input.as_ref(), record.part));
+    match answer {
+        Ok(answer) if answer == record.expected => Outcome::Correct,
+        Ok(answer) => Outcome::Wrong(answer),
+        Err(e) => Outcome::Failed(e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Outcome, Record};
+
+    #[test]
+    fn parse() -> crate::Result<()> {
+        let records = super::parse(
+            "# comment\n\n[[answer]]\nday = 5\npart = 2 # inline\ninput = \"inputs/05#.txt\"\nexpected = 1_000\n",
+        )?;
+        assert_eq!(
+            records,
+            vec![Record {
+                day: 5,
+                part: 2,
+                input: "inputs/05#.txt".into(),
+                expected: 1000u32.into(),
+            }]
+        );
+
+        assert!(matches!(
+            super::parse("[[answer]]\nday = 5\npart = 2\ninput = \"x\"\n"),
+            Err(crate::Error::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            super::parse("[[answer]]\nday = five\n"),
+            Err(crate::Error::Parse {
+                line: 2,
+                column: 7,
+                ..
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn unlisted() -> crate::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-unlisted-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        for (name, content) in [
+            ("01-example.txt", "1abc2"),
+            ("02-example.txt", "Game 1: 3 blue"),
+            ("03-example.txt", ""),
+            ("04.txt", "Card 1: 1 | 1"),
+        ] {
+            std::fs::write(dir.join(name), content)?;
+        }
+        let records = [Record {
+            day: 1,
+            part: 1,
+            input: dir.join("01-example.txt"),
+            expected: crate::Answer::from(12u32),
+        }];
+        assert_eq!(
+            super::unlisted_examples(&records, &dir)?,
+            vec![dir.join("02-example.txt")]
+        );
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn verify() -> crate::Result<()> {
+        let wrong: Vec<_> = super::load("answers.toml")?
+            .into_iter()
+            .filter_map(|record| match super::check(&record) {
+                Outcome::Correct => None,
+                outcome => Some((record, outcome)),
+            })
+            .collect();
+        assert!(wrong.is_empty(), "{wrong:#?}");
+        Ok(())
+    }
+}
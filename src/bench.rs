@@ -0,0 +1,161 @@
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use crate::{Day, Result};
+
+/// Summary of the durations of many runs of the same step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of the given samples, which must not be empty.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+        Stats {
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+        }
+    }
+}
+
+/// The timings of a day: parsing the input is measured apart from solving each part.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub parts: Vec<(u8, Stats)>,
+}
+
+/// Parses the input and solves each of the given parts `iterations` times.
+pub fn run(day: &dyn Day, input: &str, parts: &[u8], iterations: usize) -> Result<Report> {
+    let iterations = iterations.max(1);
+
+    let mut samples = Vec::with_capacity(iterations);
+    let mut parsed = None;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let result = day.parse(input)?;
+        samples.push(start.elapsed());
+        parsed = Some(result);
+    }
+    let parse = Stats::new(samples);
+    let parsed = parsed.unwrap();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let samples = (0..iterations)
+                .map(|_| {
+                    let start = Instant::now();
+                    day.solve(parsed.as_ref(), part)?;
+                    Ok(start.elapsed())
+                })
+                .collect::<Result<_>>()?;
+            Ok((part, Stats::new(samples)))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Report {
+        day: day.day(),
+        iterations,
+        parse,
+        parts,
+    })
+}
+
+impl Report {
+    fn rows(&self) -> impl Iterator<Item = (String, &Stats)> {
+        std::iter::once(("parse".to_string(), &self.parse)).chain(
+            self.parts
+                .iter()
+                .map(|(part, stats)| (format!("part {part}"), stats)),
+        )
+    }
+}
+
+/// Formats the reports as a table, one row for the parsing and one for each part of every day.
+pub fn table(reports: &[Report]) -> String {
+    let mut out = format!(
+        "{:<4} {:<7} {:>10} {:>10} {:>10} {:>10}\n",
+        "Day", "Step", "Runs", "Min", "Median", "p95"
+    );
+    for report in reports {
+        for (step, stats) in report.rows() {
+            let _ = writeln!(
+                out,
+                "{:<4} {:<7} {:>10} {:>10} {:>10} {:>10}",
+                format!("{:02}", report.day),
+                step,
+                report.iterations,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.p95),
+            );
+        }
+    }
+    out
+}
+
+/// Formats the reports as a JSON array, with one object for each row of [`table`].
+/// Durations are in nanoseconds.
+pub fn json(reports: &[Report]) -> String {
+    let rows: Vec<String> = reports
+        .iter()
+        .flat_map(|report| {
+            report.rows().map(|(step, stats)| {
+                format!(
+                    "{{\"day\":{},\"step\":\"{step}\",\"runs\":{},\"min_ns\":{},\"median_ns\":{},\"p95_ns\":{}}}",
+                    report.day,
+                    report.iterations,
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.p95.as_nanos(),
+                )
+            })
+        })
+        .collect();
+    format!("[{}]", rows.join(","))
+}
+
+pub fn format_duration(d: Duration) -> String {
+    match d.as_micros() {
+        0..=999 => format!("{}µs", d.as_micros()),
+        1_000..=999_999 => format!("{:.2}ms", d.as_secs_f64() * 1e3),
+        _ => format!("{:.2}s", d.as_secs_f64()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    #[test]
+    fn stats() {
+        let stats = super::Stats::new((1..=100).rev().map(Duration::from_millis).collect());
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+
+        let stats = super::Stats::new(vec![Duration::from_millis(7)]);
+        assert_eq!(stats.p95, Duration::from_millis(7));
+    }
+
+    #[test]
+    fn run() -> crate::Result<()> {
+        let input = crate::input_from_file("inputs/01-example1.txt")?;
+        let reports = [super::run(crate::DAYS[0], &input, &[1, 2], 3)?];
+        assert_eq!(reports[0].parts.len(), 2);
+        assert_eq!(super::table(&reports).lines().count(), 4);
+        assert!(super::json(&reports).starts_with("[{\"day\":1,\"step\":\"parse\",\"runs\":3,"));
+        Ok(())
+    }
+}
@@ -0,0 +1,435 @@
+use std::{
+    io::stdin,
+    process::ExitCode,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use adventofcode2023::{
+    answers::{self, Outcome},
+    bench, day,
+    day10::{self, Day10},
+    day11::{self, Day11},
+    diagnostic,
+    fetch::{self, Fetcher},
+    http, input_from_file, input_from_reader, progress,
+    runner::{self, Job},
+    scaffold,
+    submit::Submitter,
+    Day, Error, Result, Solution, DAYS,
+};
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day> [part] [--input <path>]
+    aoc run --all [--threads <n>] [--budget <seconds>]
+    aoc verify [--manifest <path>] [--examples]
+    aoc bench [<day> [part]] [--iterations <n>] [--json]
+    aoc fetch <day>...
+    aoc submit <day> <part> [answer]
+    aoc new <day> [title]
+    aoc render <day> [--input <path>] [--svg]
+
+Options:
+    --input <path>  read the puzzle input from <path> instead of inputs/<day>.txt,
+                    use - to read it from the standard input
+    --all           run both parts of every day in parallel, and report them once all are done
+    --threads       how many days run --all solves at the same time, one for each core by default
+    --budget        how long each part may take with run --all before it is given up, 60 by default
+    --manifest      check the answers recorded in <path> instead of answers.toml
+    --examples      only check the examples, and that every inputs/*-example*.txt is in the manifest
+    --iterations    how many times each step is run by bench, 100 by default
+    --json          print the benchmark as JSON instead of a table
+    --svg           draw the map of day 10 as an SVG image instead
+
+fetch downloads the inputs into inputs/<day>.txt, unless they are already there.
+submit solves the part on inputs/<day>.txt, unless the answer is given, and records the verdict
+in submissions.tsv.
+Both need the session cookie of adventofcode.com in AOC_SESSION or in .aoc-session.
+
+new creates src/<day>.rs from a template, empty inputs and registers the day in src/lib.rs.
+render draws the map of days 10 and 11 with the tiles that matter in colour, when printing to a terminal.";
+
+const DEFAULT_BUDGET: Duration = Duration::from_secs(60);
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("submit") => submit(&args[1..]),
+        Some("new") => new(&args[1..]),
+        Some("render") => render(&args[1..]),
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            ExitCode::SUCCESS
+        }
+        _ => usage(),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn run(args: &[String]) -> ExitCode {
+    let mut all = false;
+    let mut threads = None;
+    let mut budget = None;
+    let mut input = None;
+    let mut positional = vec![];
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--threads" => match it.next().and_then(|n| n.parse().ok()) {
+                Some(n) => threads = Some(n),
+                None => return usage(),
+            },
+            "--budget" => match it
+                .next()
+                .and_then(|n| n.parse().ok())
+                .filter(|&secs: &f64| secs > 0.0 && secs.is_finite())
+            {
+                Some(secs) => budget = Some(Duration::from_secs_f64(secs)),
+                None => return usage(),
+            },
+            "--input" => match it.next() {
+                Some(path) => input = Some(path.as_str()),
+                None => return usage(),
+            },
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    if all {
+        if !positional.is_empty() || input.is_some() {
+            return usage();
+        }
+        let start = Instant::now();
+        let runs = runner::run(
+            Job::all(&DAYS),
+            threads.unwrap_or_else(runner::default_threads),
+            Some(budget.unwrap_or(DEFAULT_BUDGET)),
+        );
+        print!("{}", runner::report(&runs, start.elapsed()));
+        return match runs.iter().all(|run| run.answer.is_ok()) {
+            true => ExitCode::SUCCESS,
+            false => ExitCode::FAILURE,
+        };
+    }
+    if threads.is_some() || budget.is_some() {
+        return usage();
+    }
+
+    let (day, parts) = match select(&positional) {
+        Ok(Some(selected)) => selected,
+        Ok(None) => return usage(),
+        Err(code) => return code,
+    };
+
+    let input = match input {
+        Some("-") => input_from_reader(stdin().lock()),
+        Some(path) => input_from_file(path),
+        None => input_from_file(default_input(day)),
+    };
+    match input.and_then(|i| solve(day, &parts, &i)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Day {:02}: {e}", day.day());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// A day along with the parts to solve.
+type Selection = (&'static dyn Day, Vec<u8>);
+
+/// Looks up the day and the parts selected by `<day> [part]`.
+fn select(positional: &[&str]) -> std::result::Result<Option<Selection>, ExitCode> {
+    let (d, parts) = match positional {
+        [d] => (d, vec![1, 2]),
+        [d, p @ ("1" | "2")] => (d, vec![p.parse().unwrap()]),
+        _ => return Ok(None),
+    };
+    match d.parse().ok().and_then(day) {
+        Some(day) => Ok(Some((day, parts))),
+        None => {
+            eprintln!("No solution for day {d}");
+            Err(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn bench(args: &[String]) -> ExitCode {
+    let mut iterations = 100;
+    let mut json = false;
+    let mut positional = vec![];
+
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--iterations" => match it.next().and_then(|n| n.parse().ok()) {
+                Some(n) => iterations = n,
+                None => return usage(),
+            },
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let selected: Vec<Selection> = match select(&positional) {
+        Ok(Some(selected)) => vec![selected],
+        Ok(None) if positional.is_empty() => DAYS.iter().map(|&d| (d, vec![1, 2])).collect(),
+        Ok(None) => return usage(),
+        Err(code) => return code,
+    };
+
+    let mut code = ExitCode::SUCCESS;
+    let mut reports = vec![];
+    for (day, parts) in selected {
+        match input_from_file(default_input(day))
+            .and_then(|input| bench::run(day, &input, &parts, iterations))
+        {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                eprintln!("Day {:02}: {e}", day.day());
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    match json {
+        true => println!("{}", bench::json(&reports)),
+        false => print!("{}", bench::table(&reports)),
+    }
+    code
+}
+
+fn fetch(args: &[String]) -> ExitCode {
+    let Ok(days) = args
+        .iter()
+        .map(|d| d.parse())
+        .collect::<std::result::Result<Vec<u8>, _>>()
+    else {
+        return usage();
+    };
+    if days.is_empty() || days.iter().any(|d| !(1..=25).contains(d)) {
+        return usage();
+    }
+
+    let fetcher = Fetcher::new(http::Auto, fetch::session().ok());
+    let mut code = ExitCode::SUCCESS;
+    for day in days {
+        match fetcher.fetch(day) {
+            Ok(path) => println!("Day {day:02}: {}", path.display()),
+            Err(e) => {
+                eprintln!("Day {day:02}: {e}");
+                code = ExitCode::FAILURE;
+            }
+        }
+    }
+    code
+}
+
+fn submit(args: &[String]) -> ExitCode {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (d, part, answer) = match args[..] {
+        [d, p @ ("1" | "2")] => (d, p.parse().unwrap(), None),
+        [d, p @ ("1" | "2"), answer] => (d, p.parse().unwrap(), answer.parse().ok()),
+        _ => return usage(),
+    };
+    let Some(day) = d.parse().ok().and_then(day) else {
+        eprintln!("No solution for day {d}");
+        return ExitCode::FAILURE;
+    };
+
+    let verdict = match answer {
+        Some(answer) => Ok(answer),
+        None => input_from_file(default_input(day))
+            .and_then(|input| day.parse(&input))
+            .and_then(|input| day.solve(input.as_ref(), part)),
+    }
+    .and_then(|answer| {
+        let verdict =
+            Submitter::new(http::Auto, fetch::session()?).submit(day.day(), part, &answer)?;
+        Ok((answer, verdict))
+    });
+    match verdict {
+        Ok((answer, verdict)) => {
+            println!("Day {:02} part {part}: {answer} is {verdict}", day.day());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Day {:02}: {e}", day.day());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn new(args: &[String]) -> ExitCode {
+    let Some(day) = args.first().and_then(|d| d.parse().ok()) else {
+        return usage();
+    };
+    let title = match args[1..].join(" ") {
+        title if title.is_empty() => "TODO".to_string(),
+        title => title,
+    };
+
+    match scaffold::create(".", day, &title) {
+        Ok(paths) => {
+            for path in paths {
+                println!("{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Day {day:02}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn render(args: &[String]) -> ExitCode {
+    let mut svg = false;
+    let mut path = None;
+    let mut positional = vec![];
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--svg" => svg = true,
+            "--input" => match it.next() {
+                Some(p) => path = Some(p.as_str()),
+                None => return usage(),
+            },
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    let [d] = positional[..] else {
+        return usage();
+    };
+    let Some(day) = d.parse().ok().and_then(day) else {
+        eprintln!("No solution for day {d}");
+        return ExitCode::FAILURE;
+    };
+
+    let input = match path {
+        Some("-") => input_from_reader(stdin().lock()),
+        Some(path) => input_from_file(path),
+        None => input_from_file(default_input(day)),
+    };
+    let drawing = input.and_then(|input| match day.day() {
+        10 if svg => day10::svg(&<Day10 as Solution>::parse(&input)?),
+        _ if svg => Err(Error::Invalid(format!(
+            "Day {} cannot be drawn as SVG",
+            day.day()
+        ))),
+        10 => Ok(day10::render(&<Day10 as Solution>::parse(&input)?)?.to_string()),
+        11 => Ok(day11::render(&<Day11 as Solution>::parse(&input)?).to_string()),
+        _ => Err(Error::Invalid(format!("Day {} cannot be drawn", day.day()))),
+    });
+    match drawing {
+        Ok(drawing) => {
+            print!("{drawing}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Day {:02}: {e}", day.day());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn verify(args: &[String]) -> ExitCode {
+    let mut manifest = "answers.toml";
+    let mut examples = false;
+    let mut it = args.iter();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--examples" => examples = true,
+            "--manifest" => match it.next() {
+                Some(path) => manifest = path,
+                None => return usage(),
+            },
+            _ => return usage(),
+        }
+    }
+    let records = match answers::load(manifest) {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("{manifest}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let records: Vec<_> = records
+        .into_iter()
+        .filter(|record| !examples || record.is_example())
+        .collect();
+
+    let (mut correct, mut wrong) = (0, 0);
+    for record in &records {
+        let name = record.name();
+        match answers::check(record) {
+            Outcome::Correct => {
+                println!("ok   {name}: {}", record.expected);
+                correct += 1;
+            }
+            Outcome::Wrong(answer) => {
+                println!("FAIL {name}: expected {}, got {answer}", record.expected);
+                wrong += 1;
+            }
+            Outcome::Failed(e) => {
+                println!("FAIL {name}: {e}");
+                wrong += 1;
+            }
+        }
+    }
+    if examples {
+        match answers::unlisted_examples(&records, "inputs") {
+            Ok(unlisted) => {
+                for path in &unlisted {
+                    println!("FAIL {}: not in {manifest}", path.display());
+                }
+                wrong += unlisted.len();
+            }
+            Err(e) => {
+                println!("FAIL inputs: {e}");
+                wrong += 1;
+            }
+        }
+    }
+
+    println!("{correct} correct, {wrong} wrong");
+    match wrong {
+        0 => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+fn default_input(day: &dyn Day) -> String {
+    format!("inputs/{:02}.txt", day.day())
+}
+
+fn solve(day: &dyn Day, parts: &[u8], input: &str) -> Result<()> {
+    let bar = Arc::new(progress::Bar::new(format!("Day {:02}", day.day())));
+    diagnostic::check(day, input)?;
+    let start = Instant::now();
+    let input = progress::scope(bar.clone(), || day.parse(input))?;
+    let parse_time = start.elapsed();
+
+    for &part in parts {
+        let start = Instant::now();
+        let answer = progress::scope(bar.clone(), || day.solve(input.as_ref(), part))?;
+        bar.clear();
+        println!(
+            "Day {:02} part {part}: {answer} ({})",
+            day.day(),
+            bench::format_duration(parse_time + start.elapsed())
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{Error, Result};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Token>> = const { RefCell::new(None) };
+}
+
+/// Tells a solver when to give up: once it is cancelled, or once its deadline has passed.
+/// Clones share the same state, so the runner can cancel a solver running on another thread.
+///
+/// Solvers receive the token of the thread they run on through [`check`], which they should call
+/// regularly in their long loops.
+#[derive(Debug, Clone)]
+pub struct Token {
+    cancelled: Arc<AtomicBool>,
+    start: Instant,
+    budget: Option<Duration>,
+}
+
+impl Default for Token {
+    fn default() -> Self {
+        Token {
+            cancelled: Arc::default(),
+            start: Instant::now(),
+            budget: None,
+        }
+    }
+}
+
+impl Token {
+    /// A token that is only cancelled by [`Token::cancel`].
+    pub fn new() -> Self {
+        Token::default()
+    }
+
+    /// A token that is cancelled `budget` from now.
+    pub fn with_budget(budget: Duration) -> Self {
+        Token {
+            budget: Some(budget),
+            ..Token::default()
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self.budget.is_some_and(|b| self.start.elapsed() >= b)
+    }
+
+    /// Fails with [`Error::TimedOut`] once the token is cancelled.
+    pub fn check(&self) -> Result<()> {
+        match self.is_cancelled() {
+            true => Err(Error::TimedOut(
+                self.budget.unwrap_or_else(|| self.start.elapsed()),
+            )),
+            false => Ok(()),
+        }
+    }
+
+    /// Runs `f` with this token installed on the current thread, so that [`check`] uses it.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        // Restore the previous token even if `f` panics.
+        struct Restore(Option<Token>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+        let _restore = Restore(previous);
+        f()
+    }
+}
+
+/// Checks the token installed on the current thread by [`Token::scope`], if any.
+pub fn check() -> Result<()> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(token) => token.check(),
+        None => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::Token;
+
+    #[test]
+    fn scope() {
+        assert!(super::check().is_ok());
+
+        let token = Token::new();
+        token.scope(|| {
+            assert!(super::check().is_ok());
+            token.cancel();
+            assert!(super::check().is_err());
+            Token::new().scope(|| assert!(super::check().is_ok()));
+            assert!(super::check().is_err());
+        });
+        assert!(super::check().is_ok());
+
+        let token = Token::with_budget(Duration::ZERO);
+        assert_eq!(
+            token.check().unwrap_err().to_string(),
+            "timed out after 0 s"
+        );
+    }
+}
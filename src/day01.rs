@@ -1,3 +1,5 @@
+use crate::{Error, Result, Solution};
+
 /// Day 1: Trebuchet?!
 ///
 /// Something is wrong with global snow production, and you've been selected to take a look.
@@ -38,16 +40,8 @@
 /// Adding these together produces 142.
 ///
 /// Consider your entire calibration document. What is the sum of all of the calibration values?
-pub fn problem1(input: Vec<String>) -> u32 {
-    input
-        .iter()
-        .map(|line| {
-            let mut digits = line.chars().filter_map(|c| c.to_digit(10));
-            let first = digits.next().unwrap_or(0);
-            let last = digits.last().unwrap_or(first);
-            first * 10 + last
-        })
-        .sum()
+pub fn problem1(input: Vec<String>) -> Result<u32> {
+    Day01::part1(&Day01::parse(&input.join("\n"))?)
 }
 
 /// Your calculation isn't quite right. It looks like some of the digits are actually spelled out with
@@ -66,22 +60,63 @@ pub fn problem1(input: Vec<String>) -> u32 {
 /// ---
 /// In this example, the calibration values are 29, 83, 13, 24, 42, 14, and 76.
 /// Adding these together produces 281.
-pub fn problem2(input: Vec<String>) -> u32 {
-    input
-        .iter()
-        .map(|line| {
-            let mut digits = line
-                .chars()
-                .enumerate()
-                .filter_map(|(i, c)| match c.to_digit(10) {
-                    Some(d) => Some(d),
-                    None => do_match(&line[i..]),
-                });
-            let first = digits.next().unwrap_or(0);
-            let last = digits.last().unwrap_or(first);
-            first * 10 + last
-        })
-        .sum()
+pub fn problem2(input: Vec<String>) -> Result<u32> {
+    Day01::part2(&Day01::parse(&input.join("\n"))?)
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
+
+    type Input = String;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        for (idx, line) in input.lines().enumerate() {
+            if let Some(i) = line.find(|c: char| !c.is_ascii_alphanumeric()) {
+                return Err(Error::at(
+                    idx,
+                    line,
+                    &line[i..],
+                    "expected letters and digits",
+                ));
+            }
+        }
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        Ok(input
+            .lines()
+            .map(|line| {
+                let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+                let first = digits.next().unwrap_or(0);
+                let last = digits.next_back().unwrap_or(first);
+                first * 10 + last
+            })
+            .sum())
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        Ok(input
+            .lines()
+            .map(|line| {
+                let mut digits =
+                    line.chars()
+                        .enumerate()
+                        .filter_map(|(i, c)| match c.to_digit(10) {
+                            Some(d) => Some(d),
+                            None => do_match(&line[i..]),
+                        });
+                let first = digits.next().unwrap_or(0);
+                let last = digits.last().unwrap_or(first);
+                first * 10 + last
+            })
+            .sum())
+    }
 }
 
 const DIGITS: [&str; 9] = [
@@ -100,33 +135,37 @@ fn do_match(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/01-example1.txt")),
+            super::problem1(crate::lines_from_file("inputs/01-example1.txt")?)?,
             142
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/01-example2.txt")),
+            super::problem2(crate::lines_from_file("inputs/01-example2.txt")?)?,
             281
         );
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/01.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/01.txt")?)?;
         println!("Solution for day 01 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/01.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/01.txt")?)?;
         println!("Solution for day 01 problem 2: {}", solution);
+        Ok(())
     }
 }
@@ -1,3 +1,8 @@
+use crate::{
+    parse::{self, Line},
+    Error, Result, Solution,
+};
+
 /// Day 2: Cube Conundrum
 ///
 /// You're launched high into the atmosphere! The apex of your trajectory just barely reaches
@@ -21,7 +26,7 @@
 /// list of subsets of cubes that were revealed from the bag (like 3 red, 5 green, 4 blue).
 ///
 /// For example, the record of a few games might look like this:
-/// ```
+/// ```text
 /// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 /// Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 /// Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -43,20 +48,8 @@
 ///
 /// Determine which games would have been possible if the bag had been loaded with only 12 red cubes,
 /// 13 green cubes, and 14 blue cubes. What is the sum of the IDs of those games?
-pub fn problem1(games: Vec<String>) -> u32 {
-    games
-        .iter()
-        .map(|s| Game::from(s.as_str()))
-        .fold(0, |sum, game| {
-            if game
-                .reveals
-                .iter()
-                .all(|(r, g, b)| r <= &12 && g <= &13 && b <= &14)
-            {
-                return sum + game.id;
-            }
-            sum
-        })
+pub fn problem1(games: Vec<String>) -> Result<u32> {
+    Day02::part1(&Day02::parse(&games.join("\n"))?)
 }
 
 pub type RGB = (u32, u32, u32);
@@ -65,34 +58,40 @@ pub struct Game {
     reveals: Vec<RGB>,
 }
 
-impl From<&str> for Game {
-    fn from(s: &str) -> Self {
-        // I assume the input is well-formed, otherwise this will panic.
-        // The structure of an input line is:
-        // Game <id>: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-        let mut s = s[5..].split(": ");
-
-        Game {
-            id: s.next().unwrap().parse().unwrap(),
-            reveals: s
-                .next()
-                .unwrap()
+impl Game {
+    /// Parses a line of the input, whose structure is:
+    /// Game <id>: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+    fn parse(line: Line) -> Result<Self> {
+        let (label, rest) = line.labelled()?;
+        let id = label
+            .strip_prefix("Game ")
+            .ok_or_else(|| line.error(label, "expected 'Game <id>'"))?;
+
+        Ok(Game {
+            id: line.number(id)?,
+            reveals: rest
                 .split("; ")
                 .map(|reveal| {
-                    reveal.split(", ").fold((0, 0, 0), |mut rgb, color| {
-                        let mut i = color.split(' ');
-                        let count: u32 = i.next().unwrap().trim().parse().unwrap();
-                        match i.next() {
-                            Some("red") => rgb.0 += count,
-                            Some("green") => rgb.1 += count,
-                            Some("blue") => rgb.2 += count,
-                            _ => panic!("Invalid color"),
-                        };
-                        rgb
-                    })
+                    reveal
+                        .split(", ")
+                        .try_fold((0u32, 0u32, 0u32), |mut rgb, color| {
+                            let (count_token, name) =
+                                line.split_once(color, " ", "<count> <color>")?;
+                            let count: u32 = line.number(count_token)?;
+                            let total = match name {
+                                "red" => &mut rgb.0,
+                                "green" => &mut rgb.1,
+                                "blue" => &mut rgb.2,
+                                _ => return Err(line.error(name, "invalid color")),
+                            };
+                            *total = total
+                                .checked_add(count)
+                                .ok_or_else(|| line.error(count_token, "number too large"))?;
+                            Ok(rgb)
+                        })
                 })
-                .collect(),
-        }
+                .collect::<Result<_>>()?,
+        })
     }
 }
 
@@ -104,7 +103,7 @@ impl From<&str> for Game {
 /// what is the fewest number of cubes of each color that could have been in the bag to make the game possible?
 ///
 /// Again consider the example games from earlier:
-/// ```
+/// ```text
 /// Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
 /// Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
 /// Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -117,59 +116,114 @@ impl From<&str> for Game {
 /// - Game 3 must have been played with at least 20 red, 13 green, and 6 blue cubes.
 /// - Game 4 required at least 14 red, 3 green, and 15 blue cubes.
 /// - Game 5 needed no fewer than 6 red, 3 green, and 2 blue cubes in the bag.
+///
 /// The power of a set of cubes is equal to the numbers of red, green, and blue cubes multiplied together.
 /// The power of the minimum set of cubes in game 1 is 48. In games 2-5 it was 12, 1560, 630, and 36, respectively.
 /// Adding up these five powers produces the sum 2286.
 ///
 /// For each game, find the minimum set of cubes that must have been present. What is the sum of the power of these sets?
-pub fn problem2(games: Vec<String>) -> u32 {
-    games
-        .iter()
-        .map(|s| {
-            Game::from(s.as_str())
-                .reveals
-                .iter()
-                .fold((0, 0, 0), |mut acc, (r, g, b)| {
+pub fn problem2(games: Vec<String>) -> Result<u32> {
+    Day02::part2(&Day02::parse(&games.join("\n"))?)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    type Input = Vec<Game>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(games: &str) -> Result<Self::Input> {
+        parse::lines(games).map(Game::parse).collect()
+    }
+
+    fn part1(games: &Self::Input) -> Result<u32> {
+        games
+            .iter()
+            .filter(|game| {
+                game.reveals
+                    .iter()
+                    .all(|(r, g, b)| r <= &12 && g <= &13 && b <= &14)
+            })
+            .try_fold(0u32, |sum, game| sum.checked_add(game.id))
+            .ok_or_else(overflow)
+    }
+
+    fn part2(games: &Self::Input) -> Result<u32> {
+        games
+            .iter()
+            .map(|game| {
+                game.reveals.iter().fold((0, 0, 0), |mut acc, (r, g, b)| {
                     acc.0 = acc.0.max(*r);
                     acc.1 = acc.1.max(*g);
                     acc.2 = acc.2.max(*b);
                     acc
                 })
-        })
-        .map(|(r, g, b)| r * g * b)
-        .sum()
+            })
+            .try_fold(0u32, |sum, (r, g, b)| {
+                sum.checked_add(r.checked_mul(g)?.checked_mul(b)?)
+            })
+            .ok_or_else(overflow)
+    }
+}
+
+fn overflow() -> Error {
+    Error::Invalid("The sum overflows".into())
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/02-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/02-example.txt")?)?,
             8
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/02-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/02-example.txt")?)?,
             2286
         );
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() {
+        use crate::Solution;
+
+        let max = u32::MAX;
+        let input = format!("Game {max}: 1 red\nGame {max}: 1 red\n");
+        assert!(super::Day02::solve1(&input).is_err());
+        let input = format!("Game 1: {max} red, {max} blue, {max} green\n");
+        assert!(super::Day02::solve2(&input).is_err());
+        let input = "Game 1: 4294967295 red, 1 red\n";
+        assert_eq!(
+            super::Day02::solve1(input).unwrap_err().to_string(),
+            "line 1, column 25: number too large"
+        );
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/02.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/02.txt")?)?;
         println!("Solution for day 02 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/02.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/02.txt")?)?;
         println!("Solution for day 02 problem 2: {}", solution);
+        Ok(())
     }
 }
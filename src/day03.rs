@@ -1,3 +1,9 @@
+use crate::{
+    geometry::{Bounds, Point},
+    grid::Grid,
+    Error, Result, Solution,
+};
+
 /// Day 3: Gear Ratios
 ///
 /// You and the Elf eventually reach a gondola lift station; he says the gondola lift will
@@ -21,7 +27,7 @@
 /// (Periods (.) do not count as a symbol.)
 ///
 /// Here is an example engine schematic:
-/// ```
+/// ```text
 /// 467..114..
 /// ...*......
 /// ..35..633.
@@ -39,40 +45,8 @@
 ///
 /// Of course, the actual engine schematic is much larger.
 /// What is the sum of all of the part numbers in the engine schematic?
-pub fn problem1(mut input: Vec<String>) -> u32 {
-    input.iter_mut().for_each(|line| line.push('.')); // blink blink we avoid the edge case where the number is at the end of the line
-    input
-        .iter()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .fold((0, 0, 0), |(count, start, mut sum), (j, char)| match char {
-                    '0'..='9' => (count * 10 + char.to_digit(10).unwrap(), start, sum),
-                    _ => {
-                        if count > 0
-                            && (char != '.' || // right
-                            (start > 0 && line.chars().nth(start - 1).is_some_and(|c| c != '.')) ||
-                            i.checked_sub(1) // top index
-                                .and_then(|x| input.get(x)) // top line
-                                .is_some_and(|top| top
-                                    .get(start.saturating_sub(1)..=j)
-                                    .is_some_and(|s| s.chars().any(|c| !matches!(c, '0'..='9' | '.')))
-                                ) ||
-                            input
-                                .get(i + 1) // bottom line
-                                .is_some_and(|btm| btm
-                                    .get(start.saturating_sub(1)..=j)
-                                    .is_some_and(|s| s.chars().any(|c| !matches!(c, '0'..='9' | '.')))
-                                )) {
-                            sum += count;
-                        }
-                        (0, j + 1, sum)
-                    }
-                })
-                .2
-        })
-        .sum()
+pub fn problem1(input: Vec<String>) -> Result<u32> {
+    Day03::part1(&Day03::parse(&input.join("\n"))?)
 }
 
 /// The engineer finds the missing part and installs it in the engine!
@@ -93,7 +67,7 @@ pub fn problem1(mut input: Vec<String>) -> u32 {
 /// so that the engineer can figure out which gear needs to be replaced.
 ///
 /// Consider the same engine schematic again:
-/// ```
+/// ```text
 /// 467..114..
 /// ...*......
 /// ..35..633.
@@ -112,102 +86,142 @@ pub fn problem1(mut input: Vec<String>) -> u32 {
 /// Adding up all of the gear ratios produces 467835.
 ///
 /// What is the sum of all of the gear ratios in your engine schematic?
-pub fn problem2(input: Vec<String>) -> u32 {
-    input
-        .iter()
-        .enumerate()
-        .map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                // filter_map gives random errors with lifetimes
-                .map(|(j, char)| match char {
-                    '*' => {
-                        [
-                            get_num(&line.chars().collect(), j),
-                            i.checked_sub(1)
-                                .and_then(|idx| {
-                                    input.get(idx).map(|v| get_num(&v.chars().collect(), j))
-                                })
-                                .unwrap_or_default(),
-                            input
-                                .get(i + 1)
-                                .map(|btm| get_num(&btm.chars().collect(), j))
-                                .unwrap_or_default(),
-                        ]
-                        .into_iter()
-                        .flatten()
-                        .enumerate()
-                        .fold((0, 0), |acc, (idx, num)| match idx {
-                            0 => (num, 0),
-                            1 => (0, acc.0 * num),
-                            _ => (0, 0),
-                        })
-                        .1
-                    }
-
-                    _ => 0,
-                })
-                .sum::<u32>()
-        })
-        .sum()
+pub fn problem2(input: Vec<String>) -> Result<u32> {
+    Day03::part2(&Day03::parse(&input.join("\n"))?)
 }
 
-fn get_num(ch: &Vec<char>, at: usize) -> Vec<u32> {
-    if !matches!(ch.get(at), Some(c) if c.is_ascii_digit()) {
-        let mut res = vec![];
-        if at > 0 && matches!(ch.get(at - 1), Some(c) if c.is_ascii_digit()) {
-            res.extend(get_num(ch, at - 1));
-        }
-        if at < ch.len() - 1 && matches!(ch.get(at + 1), Some(c) if c.is_ascii_digit()) {
-            res.extend(get_num(ch, at + 1));
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    type Input = Grid<char>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let grid = Grid::parse(input, "expected a digit or a symbol", |c| {
+            (c.is_ascii_digit() || c.is_ascii_punctuation()).then_some(c)
+        })?;
+        for (i, line) in input.lines().enumerate() {
+            for number in line.split(|c: char| !c.is_ascii_digit()) {
+                if number.parse::<u32>().is_err() && !number.is_empty() {
+                    return Err(Error::at(i, line, number, "number too large"));
+                }
+            }
         }
-        return res;
+        Ok(grid)
     }
 
-    let l = (0..at)
-        .rev()
-        .find(|&i| !matches!(ch.get(i), Some(c) if c.is_ascii_digit()))
-        .map(|i| i + 1);
-    let mut res = 0;
-    for i in l.unwrap_or_default().. {
-        match ch.get(i) {
-            Some(c) if c.is_ascii_digit() => res = res * 10 + c.to_digit(10).unwrap(),
-            _ => break,
-        }
+    fn part1(input: &Self::Input) -> Result<u32> {
+        let symbols: Vec<Point<usize>> =
+            input.find_all(|&c| is_symbol(c)).map(Point::from).collect();
+        numbers(input)
+            .filter(|(bounds, _)| symbols.iter().any(|&s| bounds.grow(1).contains(s)))
+            .try_fold(0u32, |sum, (_, n)| sum.checked_add(n))
+            .ok_or_else(overflow)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        let numbers: Vec<_> = numbers(input).collect();
+        input
+            .find_all(|&c| c == '*')
+            .map(|at| {
+                let adjacent: Vec<u32> = numbers
+                    .iter()
+                    .filter(|(bounds, _)| bounds.grow(1).contains(at.into()))
+                    .map(|&(_, n)| n)
+                    .collect();
+                match adjacent[..] {
+                    [a, b] => a.checked_mul(b),
+                    _ => Some(0),
+                }
+            })
+            .try_fold(0u32, |sum, ratio| sum.checked_add(ratio?))
+            .ok_or_else(overflow)
     }
-    vec![res]
+}
+
+fn overflow() -> Error {
+    Error::Invalid("The sum overflows".into())
+}
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+/// The numbers of the schematic, along with the cells they cover.
+fn numbers(grid: &Grid<char>) -> impl Iterator<Item = (Bounds<usize>, u32)> + '_ {
+    grid.rows().enumerate().flat_map(|(i, row)| {
+        let mut numbers = vec![];
+        let mut j = 0;
+        while j < row.len() {
+            let len = row[j..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len > 0 {
+                let n = row[j..j + len]
+                    .iter()
+                    .fold(0, |n, c| n * 10 + c.to_digit(10).unwrap());
+                let bounds = Bounds {
+                    min: Point::new(j, i),
+                    max: Point::new(j + len - 1, i),
+                };
+                numbers.push((bounds, n));
+            }
+            j += len + 1;
+        }
+        numbers
+    })
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/03-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/03-example.txt")?)?,
             4361
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/03-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/03-example.txt")?)?,
             467835
         );
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() {
+        use crate::Solution;
+
+        assert_eq!(
+            super::Day03::parse("99999999999999999999")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: number too large"
+        );
+        assert!(super::Day03::solve1("4294967295*1").is_err());
+        assert!(super::Day03::solve2("65536*65536").is_err());
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/03.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/03.txt")?)?;
         println!("Solution for day 03 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/03.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/03.txt")?)?;
         println!("Solution for day 03 problem 2: {}", solution);
+        Ok(())
     }
 }
@@ -1,3 +1,7 @@
+use std::collections::HashSet;
+
+use crate::{parse, Error, Result, Solution};
+
 /// Day 4: Scratchcards
 ///
 /// The gondola takes you up. Strangely, though, the ground doesn't seem to be coming with you; you're not
@@ -28,7 +32,7 @@
 /// the first doubles the point value of that card.
 ///
 /// For example:
-/// ```
+/// ```text
 /// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 /// Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 /// Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -44,28 +48,12 @@
 /// - Card 4 has one winning number (84), so it is worth 1 point.
 /// - Card 5 has no winning numbers, so it is worth no points.
 /// - Card 6 has no winning numbers, so it is worth no points.
+///
 /// So, in this example, the Elf's pile of scratchcards is worth 13 points.
 ///
 /// Take a seat in the large pile of colorful cards. How many points are they worth in total?
-pub fn problem1(input: Vec<String>) -> u32 {
-    input
-        .into_iter()
-        .map(|s| {
-            let s = s.split(':').nth(1).unwrap();
-            let v = s
-                .split('|')
-                .map(|s| {
-                    s.split(' ')
-                        .filter_map(|s| s.trim().parse::<u32>().ok())
-                        .collect::<std::collections::HashSet<u32>>()
-                })
-                .collect::<Vec<_>>();
-            match v[0].intersection(&v[1]).count() {
-                0 => 0,
-                n => 2u32.pow((n - 1) as u32),
-            }
-        })
-        .sum()
+pub fn problem1(input: Vec<String>) -> Result<u32> {
+    Day04::part1(&Day04::parse(&input.join("\n"))?)
 }
 
 /// Just as you're about to report your findings to the Elf, one of you realizes that the rules have actually
@@ -83,7 +71,7 @@ pub fn problem1(input: Vec<String>) -> u32 {
 /// you to win any more cards. (Cards will never make you copy a card past the end of the table.)
 ///
 /// This time, the above example goes differently:
-/// ```
+/// ```text
 /// Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
 /// Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
 /// Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -108,59 +96,109 @@ pub fn problem1(input: Vec<String>) -> u32 {
 ///
 /// Process all of the original and copied scratchcards until no more scratchcards are won.
 /// Including the original set of scratchcards, how many total scratchcards do you end up with?
-pub fn problem2(input: Vec<String>) -> u32 {
-    let mut counter = vec![1; input.len()];
-    input.into_iter().enumerate().for_each(|(idx, s)| {
-        let s = s.split(':').nth(1).unwrap();
-        let v = s
-            .split('|')
-            .map(|s| {
-                s.split(' ')
-                    .filter_map(|s| s.trim().parse::<u32>().ok())
-                    .collect::<std::collections::HashSet<u32>>()
+pub fn problem2(input: Vec<String>) -> Result<u32> {
+    Day04::part2(&Day04::parse(&input.join("\n"))?)
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    /// How many winning numbers each card has.
+    type Input = Vec<usize>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::lines(input)
+            .map(|line| {
+                let (_, numbers) = line.labelled()?;
+                let (winning, mine) = line.split_once(numbers, "|", "<winning> | <mine>")?;
+                let numbers =
+                    |s| -> Result<HashSet<u32>> { Ok(line.numbers(s)?.into_iter().collect()) };
+                Ok(numbers(winning)?.intersection(&numbers(mine)?).count())
             })
-            .collect::<Vec<_>>();
-        let r = v[0].intersection(&v[1]).count();
-        let amt = counter[idx];
-        for i in (idx + 1)..=(idx + r) {
-            if let Some(c) = counter.get_mut(i) {
-                *c += amt;
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        input
+            .iter()
+            .try_fold(0u32, |sum, &n| match n {
+                0 => Some(sum),
+                n => sum.checked_add(2u32.checked_pow((n - 1) as u32)?),
+            })
+            .ok_or_else(|| Error::Invalid("The points overflow".into()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        let overflow = || Error::Invalid("The number of cards overflows".into());
+        let mut counter = vec![1u32; input.len()];
+        for (idx, &r) in input.iter().enumerate() {
+            let amt = counter[idx];
+            for c in counter.iter_mut().skip(idx + 1).take(r) {
+                *c = c.checked_add(amt).ok_or_else(overflow)?;
             }
         }
-    });
-    counter.into_iter().sum()
+        counter
+            .into_iter()
+            .try_fold(0u32, |sum, c| sum.checked_add(c))
+            .ok_or_else(overflow)
+    }
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/04-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/04-example.txt")?)?,
             13
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/04-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/04-example.txt")?)?,
             30
         );
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() -> crate::Result<()> {
+        use crate::Solution;
+
+        let card = |i, n| {
+            let numbers: Vec<String> = (1..=n).map(|x: u32| x.to_string()).collect();
+            let numbers = numbers.join(" ");
+            format!("Card {i}: {numbers} | {numbers}\n")
+        };
+        let input: String = (1..=60).map(|i| card(i, 10)).collect();
+        assert_eq!(super::Day04::solve1(&input)?, 60 << 9);
+        assert!(super::Day04::solve2(&input).is_err());
+        assert!(super::Day04::solve1(&card(1, 40)).is_err());
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/04.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/04.txt")?)?;
         println!("Solution for day 04 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/04.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/04.txt")?)?;
         println!("Solution for day 04 problem 2: {}", solution);
+        Ok(())
     }
 }
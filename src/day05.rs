@@ -1,3 +1,9 @@
+use crate::{
+    interval::{Interval, OffsetMap},
+    parse::{self, Line},
+    Diagnostic, Error, Result, Solution,
+};
+
 /// Day 5: If You Give A Seed A Fertilizer
 ///
 /// You take the boat and find the gardener right where you were told he would be:
@@ -27,7 +33,7 @@
 /// by each category - that is, soil 123 and fertilizer 123 aren't necessarily related to each other.
 ///
 /// For example:
-/// ```
+/// ```text
 /// seeds: 79 14 55 13
 ///
 /// seed-to-soil map:
@@ -75,7 +81,7 @@
 /// the destination range start, the source range start, and the range length.
 ///
 /// Consider again the example seed-to-soil map:
-/// ```
+/// ```text
 /// 50 98 2
 /// 52 50 48
 /// ```
@@ -93,7 +99,7 @@
 /// So, seed number 10 corresponds to soil number 10.
 ///
 /// So, the entire list of seed numbers and their corresponding soil numbers looks like this:
-/// ```
+/// ```text
 /// seed  soil
 /// 0     0
 /// 1     1
@@ -113,6 +119,7 @@
 /// - Seed number 14 corresponds to soil number 14.
 /// - Seed number 55 corresponds to soil number 57.
 /// - Seed number 13 corresponds to soil number 13.
+///
 /// The gardener and his team want to get started as soon as possible, so they'd like to know the closest
 /// location that needs a seed. Using these maps, find the lowest location number that corresponds to any
 /// of the initial seeds. To do this, you'll need to convert each seed number through other categories
@@ -121,51 +128,33 @@
 /// - Seed 14, soil 14, fertilizer 53, water 49, light 42, temperature 42, humidity 43, location 43.
 /// - Seed 55, soil 57, fertilizer 57, water 53, light 46, temperature 82, humidity 82, location 86.
 /// - Seed 13, soil 13, fertilizer 52, water 41, light 34, temperature 34, humidity 35, location 35.
+///
 /// So, the lowest location number in this example is 35.
 ///
 /// What is the lowest location number that corresponds to any of the initial seed numbers?
-pub fn problem1(input: Vec<String>) -> u64 {
-    let mut it = input.iter();
-
-    let mut current: Vec<u64> = it
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect();
+pub fn problem1(input: Vec<String>) -> Result<u64> {
+    Day05::part1(&Day05::parse(&input.join("\n"))?)
+}
 
-    solve(&mut it, &mut current)
+pub struct Almanac {
+    seeds: Vec<u64>,
+    maps: Vec<OffsetMap>,
 }
 
-fn solve(it: &mut std::slice::Iter<String>, current: &mut Vec<u64>) -> u64 {
-    println!("Current len: {}", current.len());
-    for _ in 0..8 {
-        let section = it
-            .by_ref()
-            .take_while(|l| !l.is_empty())
-            .filter_map(|l| {
-                let mut it = l.split_whitespace().filter_map(|s| s.parse::<u64>().ok());
-                match (it.next(), it.next(), it.next()) {
-                    (Some(a), Some(b), Some(c)) => Some((a, b, b + c)), // source, dest_start, dest_end
-                    _ => {
-                        println!("{l}",);
-                        None
-                    }
-                }
-            })
-            .collect::<Vec<_>>();
-
-        current.iter_mut().for_each(|c| {
-            for s in &section {
-                if *c >= s.1 && *c < s.2 {
-                    *c = s.0 + (*c - s.1);
-                    break;
-                }
-            }
-        });
+/// Reads a `<destination> <source> <length>` line as the source interval and the destination start,
+/// provided both ranges end within a `u64`.
+fn rule(line: &str) -> Option<(Interval<u64>, u64)> {
+    let numbers = line
+        .split_whitespace()
+        .map(|s| s.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match numbers[..] {
+        [a, b, c] => {
+            a.checked_add(c)?;
+            Some((Interval::new(b, b.checked_add(c)?), a))
+        }
+        _ => None,
     }
-
-    *current.iter().min().unwrap()
 }
 
 /// Everyone will starve if you only plant such a small number of seeds.
@@ -186,53 +175,190 @@ fn solve(it: &mut std::slice::Iter<String>, current: &mut Vec<u64>) -> u64 {
 ///
 /// Consider all of the initial seed numbers listed in the ranges on the first line of the almanac.
 /// What is the lowest location number that corresponds to any of the initial seed numbers?
-pub fn problem2(input: Vec<String>) -> u64 {
-    let mut it = input.iter();
+pub fn problem2(input: Vec<String>) -> Result<u64> {
+    Day05::part2(&Day05::parse(&input.join("\n"))?)
+}
 
-    let mut current: Vec<u64> = it
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect::<Vec<_>>()
-        .chunks(2)
-        .flat_map(|c| (c[0]..c[0] + c[1]).collect::<Vec<_>>())
-        .collect();
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    type Input = Almanac;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut sections = parse::sections(input).into_iter();
+        let mut first = sections.next().unwrap_or_default().into_iter();
+
+        let line = first.next().unwrap_or(Line { index: 0, text: "" });
+        let seeds = line.numbers(line.label("seeds")?)?;
+
+        // Whatever follows the seeds in the same section is read as one more map.
+        let maps = std::iter::once(first.collect())
+            .chain(sections)
+            .map(|section: Vec<Line>| {
+                section
+                    .into_iter()
+                    .filter_map(|line| match rule(line.text) {
+                        Some(rule) => Some(rule),
+                        None if line.text.ends_with(" map:") => None,
+                        None => {
+                            crate::progress::log(format!(
+                                "line {}: skipped '{}'",
+                                line.index + 1,
+                                line.text
+                            ));
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Almanac { seeds, maps })
+    }
 
-    solve(&mut it, &mut current)
+    /// Unlike [`Day05::parse`], which skips them, reports every line that is neither a map header nor a rule.
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = match Self::parse(input) {
+            Ok(_) => vec![],
+            Err(e) => vec![Diagnostic::from_error(e)],
+        };
+        diagnostics.extend(
+            parse::lines(input)
+                .skip(1)
+                .filter(|l| {
+                    !l.text.is_empty() && !l.text.ends_with(" map:") && rule(l.text).is_none()
+                })
+                .map(|l| l.error(l.text, "expected '<destination> <source> <length>'"))
+                .map(Diagnostic::from_error),
+        );
+        diagnostics
+    }
+
+    fn part1(almanac: &Self::Input) -> Result<u64> {
+        almanac
+            .seeds
+            .iter()
+            .map(|&seed| almanac.maps.iter().fold(seed, |x, map| map.get(x)))
+            .min()
+            .ok_or_else(|| Error::Invalid("There are no seeds to plant".into()))
+    }
+
+    /// Moves whole ranges of seeds through the maps at once, so only their endpoints are computed.
+    fn part2(almanac: &Self::Input) -> Result<u64> {
+        if almanac.seeds.len() % 2 != 0 {
+            return Err(Error::Invalid("The seeds must come in pairs".into()));
+        }
+
+        let total = almanac
+            .seeds
+            .chunks(2)
+            .try_fold(0u64, |total, c| total.checked_add(c[1]))
+            .ok_or_else(|| Error::Invalid("There are too many seeds to plant".into()))?;
+        let mut done = 0;
+        let mut lowest = None;
+        for range in almanac.seeds.chunks(2) {
+            crate::cancel::check()?;
+            let end = range[0].checked_add(range[1]).ok_or_else(|| {
+                Error::Invalid(format!("The seed range from {} overflows", range[0]))
+            })?;
+            let seeds = [Interval::new(range[0], end)].into_iter().collect();
+            let locations = almanac.maps.iter().fold(seeds, |set, map| map.apply(&set));
+            if let Some(low) = locations.min() {
+                lowest = Some(lowest.map_or(low, |l: u64| l.min(low)));
+            }
+            done += range[1];
+            crate::progress::report(done, total);
+        }
+
+        lowest.ok_or_else(|| Error::Invalid("There are no seeds to plant".into()))
+    }
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/05-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/05-example.txt")?)?,
             35,
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/05-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/05-example.txt")?)?,
             46,
         );
+        Ok(())
+    }
+
+    #[test]
+    fn progress() -> crate::Result<()> {
+        use crate::Solution;
+
+        let capture = std::sync::Arc::new(crate::progress::Capture::default());
+        let input = crate::input_from_file("inputs/05-example.txt")?
+            .replace("soil map:\n", "soil map:\n?\n");
+        crate::progress::scope(capture.clone(), || super::Day05::solve2(&input))?;
+        assert_eq!(*capture.progress.lock().unwrap(), vec![(14, 27), (27, 27)]);
+        assert_eq!(
+            *capture.messages.lock().unwrap(),
+            vec!["line 4: skipped '?'"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn validate() -> crate::Result<()> {
+        use crate::Solution;
+
+        let input = crate::input_from_file("inputs/05-example.txt")?;
+        assert!(super::Day05::validate(&input).is_empty());
+        let input = input
+            .replace("50 98 2", "50 98")
+            .replace("52 50 48", "52 x 48");
+        let lines: Vec<_> = super::Day05::validate(&input)
+            .iter()
+            .map(|d| d.line)
+            .collect();
+        assert_eq!(lines, vec![4, 5]);
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() -> crate::Result<()> {
+        use crate::Solution;
+
+        let input = crate::input_from_file("inputs/05-example.txt")?;
+        let max = u64::MAX;
+        let input = input.replace("seeds: 79 14 55 13", &format!("seeds: {max} 5"));
+        assert!(super::Day05::solve2(&input).is_err());
+        let input = input.replace("50 98 2", &format!("{} 98 2", max - 1));
+        assert_eq!(super::Day05::validate(&input)[0].line, 4);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/05.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/05.txt")?)?;
         println!("Solution for day 05 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        panic!("WARNING: This test takes A LOT of time to run.");
-        // let solution = super::problem2(crate::lines_from_file("inputs/05.txt"));
-        // println!("Solution for day 05 problem 2: {}", solution);
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/05.txt")?)?;
+        println!("Solution for day 05 problem 2: {}", solution);
+        Ok(())
     }
 }
@@ -1,3 +1,8 @@
+use crate::{
+    parse::{self, Line},
+    Error, Result, Solution,
+};
+
 /// Day 6: Wait For It
 ///
 /// The ferry quickly brings you across Island Island. After asking around, you discover that
@@ -23,7 +28,7 @@
 /// You can only hold the button at the start of the race, and boats don't move until the button is released.
 ///
 /// For example:
-/// ```
+/// ```text
 /// Time:      7  15   30
 /// Distance:  9  40  200
 /// ```
@@ -67,30 +72,19 @@
 /// in this example, if you multiply these values together, you get 288 (4 * 8 * 9).
 ///
 /// Determine the number of ways you could beat the record in each race. What do you get if you multiply these numbers together?
-pub fn problem1(input: Vec<String>) -> u64 {
-    let times: Vec<u64> = input[0]
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect();
-    let distances: Vec<u64> = input[1]
-        .split_whitespace()
-        .filter_map(|s| s.parse().ok())
-        .collect();
-
-    (0..times.len())
-        .map(|i| solve(times[i], distances[i]))
-        .product()
+pub fn problem1(input: Vec<String>) -> Result<u64> {
+    Day06::part1(&Day06::parse(&input.join("\n"))?)
 }
 
 /// As the race is about to start, you realize the piece of paper with race times and record distances you got earlier
 /// actually just has very bad kerning. There's really only one race - ignore the spaces between the numbers on each line.
 /// So, the example from before:
-/// ```
+/// ```text
 /// Time:      7  15   30
 /// Distance:  9  40  200
 /// ```
 /// ...now instead means this:
-/// ```
+/// ```text
 /// Time:      71530
 /// Distance:  940200
 /// ```
@@ -99,61 +93,147 @@ pub fn problem1(input: Vec<String>) -> u64 {
 /// You could hold the button anywhere from 14 to 71516 milliseconds and beat the record, a total of 71503 ways!
 ///
 /// How many ways can you beat the record in this one much longer race?
-pub fn problem2(input: Vec<String>) -> u64 {
-    let parse: Vec<u64> = input
-        .iter()
-        .filter_map(|s| {
-            s.split(':').nth(1).map(|s| {
-                s.chars()
-                    .filter(|c| !c.is_whitespace())
-                    .fold(0u64, |acc, c| match c.to_digit(10) {
-                        Some(n) => acc * 10 + n as u64,
-                        None => acc,
-                    })
-            })
-        })
-        .collect();
-
-    solve(parse[0], parse[1])
+pub fn problem2(input: Vec<String>) -> Result<u64> {
+    Day06::part2(&Day06::parse(&input.join("\n"))?)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    type Input = Races;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let numbers = |index: usize, label: &str| {
+            let line = parse::lines(input)
+                .nth(index)
+                .unwrap_or(Line { index, text: "" });
+            line.numbers(line.label(label)?)
+        };
+
+        let times = numbers(0, "Time")?;
+        let distances = numbers(1, "Distance")?;
+        if times.len() != distances.len() {
+            return Err(Error::parse(
+                2,
+                1,
+                format!(
+                    "expected {} distances, found {}",
+                    times.len(),
+                    distances.len()
+                ),
+            ));
+        }
+
+        Ok(Races { times, distances })
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64> {
+        input
+            .times
+            .iter()
+            .zip(input.distances.iter())
+            .try_fold(1u64, |product, (&t, &d)| product.checked_mul(solve(t, d)))
+            .ok_or_else(|| Error::Invalid("The product overflows".into()))
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64> {
+        Ok(solve(
+            number(&concat(&input.times))?,
+            number(&concat(&input.distances))?,
+        ))
+    }
 }
 
+/// The numbers on the `Time:` and `Distance:` lines.
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+}
+
+/// The numbers written next to each other, without the spaces in between.
+fn concat(numbers: &[u64]) -> String {
+    numbers.iter().map(u64::to_string).collect()
+}
+
+fn number(s: &str) -> Result<u64> {
+    s.parse()
+        .map_err(|_| Error::Invalid(format!("{s} is too large")))
+}
+
+/// The number of ways to beat `d` in `t`: the winning hold times are symmetric around `t / 2`,
+/// so they go from the first one `c`, found by binary search, to `t - c`.
 fn solve(t: u64, d: u64) -> u64 {
-    (1..((t + 1) / 2))
-        .find(|j| (t - j) * j > d)
-        .map(|c| 2 * (((t + 1) / 2) - c) + ((t % 2u64 == 0) as u64))
-        .unwrap_or(0)
+    let wins = |j: u64| (t - j) as u128 * j as u128 > d as u128;
+    let (mut lo, mut hi) = (1, t / 2 + 1);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match wins(mid) {
+            true => hi = mid,
+            false => lo = mid + 1,
+        }
+    }
+    match lo <= t / 2 {
+        true => t - 2 * lo + 1,
+        false => 0,
+    }
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/06-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/06-example.txt")?)?,
             288
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/06-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/06-example.txt")?)?,
             71503
         );
+        Ok(())
+    }
+
+    #[test]
+    fn edges() {
+        assert_eq!(super::solve(2, 0), 1);
+        assert_eq!(super::solve(4, 4), 0);
+        assert_eq!(super::solve(0, 0), 0);
+        assert_eq!(super::solve(u64::MAX, 0), u64::MAX - 1);
+    }
+
+    #[test]
+    fn overflow() {
+        use crate::Solution;
+
+        let input = "Time: 90000000000000000 90000000000000000\nDistance: 1 1\n";
+        assert!(super::Day06::solve1(input).is_err());
+        assert!(super::Day06::solve2(input).is_err());
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/06.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/06.txt")?)?;
         println!("Solution for day 06 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/06.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/06.txt")?)?;
         println!("Solution for day 06 problem 2: {}", solution);
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+use crate::{Error, Result, Solution};
+
 /// Day 7: Camel Cards
 ///
 /// Your all-expenses-paid trip turns out to be a one-way, five-minute ride in an airship.
@@ -52,7 +54,7 @@
 /// (and both hands have the same first and second card).
 ///
 /// To play Camel Cards, you are given a list of hands and their corresponding bid (your puzzle input). For example:
-/// ```
+/// ```text
 /// 32T3K 765
 /// T55J5 684
 /// KK677 28
@@ -74,8 +76,8 @@
 /// with its rank (765 * 1 + 220 * 2 + 28 * 3 + 684 * 4 + 483 * 5). So the total winnings in this example are 6440.
 ///
 /// Find the rank of every hand in your set. What are the total winnings?
-pub fn problem1(input: Vec<String>) -> u32 {
-    solve(input, false)
+pub fn problem1(input: Vec<String>) -> Result<u32> {
+    Day07::part1(&Day07::parse(&input.join("\n"))?)
 }
 
 /// To make things a little more interesting, the Elf introduces one additional rule.
@@ -89,7 +91,7 @@ pub fn problem1(input: Vec<String>) -> u32 {
 /// J is always treated as J, not the card it's pretending to be: JKKK2 is weaker than QQQQ2 because J is weaker than Q.
 ///
 /// Now, the above example goes very differently:
-/// ```
+/// ```text
 /// 32T3K 765
 /// T55J5 684
 /// KK677 28
@@ -103,46 +105,110 @@ pub fn problem1(input: Vec<String>) -> u32 {
 /// With the new joker rule, the total winnings in this example are 5905.
 ///
 /// Using the new joker rule, find the rank of every hand in your set. What are the new total winnings?
-pub fn problem2(input: Vec<String>) -> u32 {
-    solve(input, true)
+pub fn problem2(input: Vec<String>) -> Result<u32> {
+    Day07::part2(&Day07::parse(&input.join("\n"))?)
 }
 
-fn solve(input: Vec<String>, joker: bool) -> u32 {
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    /// The values of the cards of each hand, along with its bid.
+    type Input = Vec<(Vec<u32>, u32)>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                let err = |token: &str, message: &str| Error::at(idx, line, token, message);
+                let mut it = line.split_whitespace();
+                let hand = it.next().ok_or_else(|| err(line, "expected a hand"))?;
+                let cards: Vec<_> = hand
+                    .char_indices()
+                    .map(|(i, c)| val(c).ok_or_else(|| err(&hand[i..], "invalid card")))
+                    .collect::<Result<_>>()?;
+                if cards.len() != 5 {
+                    return Err(err(hand, "expected 5 cards"));
+                }
+                let bid = it.next().ok_or_else(|| err(hand, "expected a bid"))?;
+                let bid = bid.parse().map_err(|_| err(bid, "invalid bid"))?;
+                Ok((cards, bid))
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32> {
+        solve(input, false)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32> {
+        solve(input, true)
+    }
+}
+
+fn solve(input: &[(Vec<u32>, u32)], joker: bool) -> Result<u32> {
     let mut values: Vec<Hand> = input
         .iter()
-        .filter_map(|s| {
-            let mut it = s.split_whitespace();
-            let cards: Vec<u32> = it.next()?.chars().map(|c| val(c, joker)).collect();
-            let rank = rank(&cards);
-            let bid = it.next()?.parse().ok()?;
-            Some(Hand { cards, rank, bid })
-        })
+        .map(|(cards, bid)| Hand::new(cards, *bid, joker))
         .collect();
-    values.sort_by(|a, b| match a.rank.cmp(&b.rank) {
-        std::cmp::Ordering::Equal => a
-            .cards
-            .iter()
-            .zip(b.cards.iter())
-            .find_map(|(a, b)| match a.cmp(b) {
-                std::cmp::Ordering::Equal => None,
-                x => Some(x),
-            })
-            .unwrap_or(std::cmp::Ordering::Equal),
-        x => x,
-    });
+    values.sort();
     values
         .iter()
         .enumerate()
-        .map(|(idx, h)| h.bid * (1 + idx as u32))
-        .sum()
+        .try_fold(0u32, |sum, (idx, h)| {
+            sum.checked_add(h.bid.checked_mul(1 + idx as u32)?)
+        })
+        .ok_or_else(|| Error::Invalid("The total winnings overflow".into()))
 }
 
+/// A hand ordered by its type, then by its cards from the first one; the bid plays no part.
+#[derive(Debug)]
 struct Hand {
     cards: Vec<u32>,
     rank: u32,
     bid: u32,
 }
 
+impl Hand {
+    /// With `joker`, the jacks become jokers, which are worth 0 but count as any card for the type.
+    fn new(cards: &[u32], bid: u32, joker: bool) -> Self {
+        let cards: Vec<u32> = cards
+            .iter()
+            .map(|&c| match joker && c == 11 {
+                true => 0,
+                false => c,
+            })
+            .collect();
+        let rank = rank(&cards);
+        Hand { cards, rank, bid }
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.rank, &self.cards).cmp(&(other.rank, &other.cards))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Hand {}
+
 fn rank(cards: &[u32]) -> u32 {
     let jokers = cards.iter().filter(|x| **x == 0).count() as u32;
 
@@ -182,48 +248,119 @@ fn rank(cards: &[u32]) -> u32 {
     }
 }
 
-fn val(c: char, joker: bool) -> u32 {
+fn val(c: char) -> Option<u32> {
     match c {
-        'A' => 14,
-        'K' => 13,
-        'Q' => 12,
-        'J' => !joker as u32 * 11,
-        'T' => 10,
-        '2'..='9' => c.to_digit(10).unwrap(),
-        _ => panic!("Invalid card value"),
+        'A' => Some(14),
+        'K' => Some(13),
+        'Q' => Some(12),
+        'J' => Some(11),
+        'T' => Some(10),
+        '2'..='9' => c.to_digit(10),
+        _ => None,
     }
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/07-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/07-example.txt")?)?,
             6440
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/07-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/07-example.txt")?)?,
             5905
         );
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() {
+        use crate::Solution;
+
+        assert!(super::Day07::solve1("AAAAA 4294967295\n22345 1").is_err());
+        assert!(super::Day07::solve2("AAAAJ 2147483648\n2345J 1").is_err());
+    }
+
+    #[test]
+    fn malformed() {
+        use crate::Solution;
+
+        for (input, expected) in [
+            (
+                "32T3K 765\nT55J 684\n",
+                "line 2, column 1: expected 5 cards",
+            ),
+            (
+                "32T3K 765\nT55J5J 684\n",
+                "line 2, column 1: expected 5 cards",
+            ),
+            ("32T3X 765\n", "line 1, column 5: invalid card"),
+            ("32T3K\n", "line 1, column 1: expected a bid"),
+        ] {
+            assert_eq!(
+                super::Day07::parse(input).unwrap_err().to_string(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn total_order() {
+        use crate::{
+            property::{self, ensure},
+            Solution,
+        };
+
+        property::check(
+            |rng| property::input(7, rng).unwrap(),
+            |input| {
+                for joker in [false, true] {
+                    let hands: Vec<_> = super::Day07::parse(input)?
+                        .iter()
+                        .map(|(cards, bid)| super::Hand::new(cards, *bid, joker))
+                        .collect();
+                    for a in &hands {
+                        for b in &hands {
+                            ensure(a.cmp(b) == b.cmp(a).reverse(), || {
+                                format!("{a:?} and {b:?} are not antisymmetric")
+                            })?;
+                            ensure(a.cmp(b).is_eq() == (a.cards == b.cards), || {
+                                format!("{a:?} and {b:?} are equal but different")
+                            })?;
+                            for c in &hands {
+                                ensure(!(a < b && b < c) || a < c, || {
+                                    format!("{a:?}, {b:?} and {c:?} are not transitive")
+                                })?;
+                            }
+                        }
+                    }
+                }
+                Ok(())
+            },
+        );
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/07.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/07.txt")?)?;
         println!("Solution for day 07 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/07.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/07.txt")?)?;
         println!("Solution for day 07 problem 2: {}", solution);
+        Ok(())
     }
 }
@@ -1,3 +1,10 @@
+use crate::{
+    graph::{Graph, NodeId},
+    math,
+    parse::{self, Line},
+    Error, Result, Solution,
+};
+
 /// Day 8: Haunted Wasteland
 ///
 /// You're still riding a camel across Desert Island when you spot a sandstorm quickly approaching.
@@ -15,7 +22,7 @@
 /// You feel like AAA is where you are now, and you have to follow the left/right instructions until you reach ZZZ.
 ///
 /// This format defines each node of the network individually. For example:
-/// ```
+/// ```text
 /// RL
 ///
 /// AAA = (BBB, CCC)
@@ -25,6 +32,7 @@
 /// EEE = (EEE, EEE)
 /// GGG = (GGG, GGG)
 /// ZZZ = (ZZZ, ZZZ)
+/// ```
 ///
 /// Starting with AAA, you need to look up the next element based on the next left/right instruction in your input.
 /// In this example, start with AAA and go right (R) by choosing the right element of AAA, CCC.
@@ -33,7 +41,7 @@
 /// Of course, you might not find ZZZ right away.
 /// If you run out of left/right instructions, repeat the whole sequence of instructions as necessary:
 /// RL really means RLRLRLRLRLRLRLRL... and so on. For example, here is a situation that takes 6 steps to reach ZZZ:
-/// ```
+/// ```text
 /// LLR
 ///
 /// AAA = (BBB, BBB)
@@ -41,21 +49,8 @@
 /// ZZZ = (ZZZ, ZZZ)
 /// ```
 /// Starting at AAA, follow the left/right instructions. How many steps are required to reach ZZZ?
-pub fn problem1(input: Vec<String>) -> usize {
-    let (instructions, graph) = parse_input(input);
-
-    let mut current = "AAA";
-    let mut steps = 0;
-    while current != "ZZZ" {
-        let (left, right) = graph.get(current).unwrap();
-        current = match instructions[steps % instructions.len()] {
-            false => left,
-            true => right,
-        };
-        steps += 1;
-    }
-
-    steps
+pub fn problem1(input: Vec<String>) -> Result<usize> {
+    Day08::part1(&Day08::parse(&input.join("\n"))?)
 }
 
 /// The sandstorm is upon you and you aren't any closer to escaping the wasteland.
@@ -71,7 +66,7 @@ pub fn problem1(input: Vec<String>) -> usize {
 /// at the same time until they all simultaneously end up at nodes that end with Z.
 ///
 /// For example:
-/// ```
+/// ```text
 /// LR
 ///
 /// 11A = (11B, XXX)
@@ -100,104 +95,207 @@ pub fn problem1(input: Vec<String>) -> usize {
 ///
 /// Simultaneously start on every node that ends with A.
 /// How many steps does it take before you're only on nodes that end with Z?
-pub fn problem2(input: Vec<String>) -> usize {
-    let (instructions, graph) = parse_input(input);
-
-    graph
-        .iter()
-        .filter_map(|(name, _)| {
-            if name.ends_with('A') {
-                let mut current = name;
-                let mut steps = 0;
-                while !current.ends_with('Z') {
-                    let (left, right) = graph.get(current).unwrap();
-                    current = match instructions[steps % instructions.len()] {
-                        false => left,
-                        true => right,
-                    };
-                    steps += 1;
-                }
-                Some(steps)
-            } else {
-                None
-            }
-        })
-        .fold(1, lcm)
+pub fn problem2(input: Vec<String>) -> Result<usize> {
+    Day08::part2(&Day08::parse(&input.join("\n"))?)
 }
 
-// lowest common multiple
-fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+
+    type Input = Network;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        let (instructions, graph) = input;
+        let (Some(start), Some(end)) = (graph.id("AAA"), graph.id("ZZZ")) else {
+            return Err(Error::Invalid(
+                "The network has no AAA or no ZZZ node".into(),
+            ));
+        };
+        if !graph.reachable(start)[end] {
+            return Err(Error::Invalid("ZZZ cannot be reached from AAA".into()));
+        }
+
+        steps(instructions, graph, start, |id| id == end)?.ok_or_else(|| {
+            Error::Invalid("The instructions lead from AAA around a cycle without ZZZ".into())
+        })
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        let (instructions, graph) = input;
+        let is_end = |id| graph.name(id).ends_with('Z');
+        if !graph.nodes().any(is_end) {
+            return Err(Error::Invalid(
+                "The network has no node ending with Z".into(),
+            ));
+        }
+
+        graph
+            .nodes()
+            .filter(|&id| graph.name(id).ends_with('A'))
+            .map(|start| {
+                match graph
+                    .reachable(start)
+                    .iter()
+                    .enumerate()
+                    .any(|(id, &r)| r && is_end(id))
+                {
+                    true => steps(instructions, graph, start, is_end)?.ok_or_else(|| {
+                        Error::Invalid(format!(
+                            "The instructions lead from {} around a cycle without any node ending with Z",
+                            graph.name(start)
+                        ))
+                    }),
+                    false => Err(Error::Invalid(format!(
+                        "No node ending with Z can be reached from {}",
+                        graph.name(start)
+                    ))),
+                }
+            })
+            .try_fold(1, |acc, steps| {
+                math::lcm(acc, steps?)
+                    .ok_or_else(|| Error::Invalid("The number of steps overflows".into()))
+            })
+    }
 }
 
-// greatest common divisor
-fn gcd(a: usize, b: usize) -> usize {
-    match b == 0 {
-        true => a,
-        false => gcd(b, a % b),
+/// The number of steps it takes to go from `start` to a node that is an end by following the instructions,
+/// or `None` when they lead around a cycle without any end, which shows once a node is reached twice
+/// at the same instruction.
+fn steps(
+    instructions: &[char],
+    graph: &Graph<char>,
+    start: NodeId,
+    is_end: impl Fn(NodeId) -> bool,
+) -> Result<Option<usize>> {
+    let mut seen = vec![false; graph.len() * instructions.len()];
+    let mut current = start;
+    let mut steps = 0;
+    while !is_end(current) {
+        let i = steps % instructions.len();
+        if i == 0 {
+            crate::cancel::check()?;
+        }
+        if std::mem::replace(&mut seen[current * instructions.len() + i], true) {
+            return Ok(None);
+        }
+        current = graph.follow(current, &instructions[i]).unwrap();
+        steps += 1;
     }
+    Ok(Some(steps))
 }
 
-fn parse_input(
-    input: Vec<String>,
-) -> (
-    Vec<bool>,
-    std::collections::HashMap<String, (String, String)>,
-) {
-    (
-        input[0]
-            .chars()
-            .map(|c| match c {
-                'L' => false,
-                'R' => true,
-                c => panic!("Invalid instruction: '{c}'"),
-            })
-            .collect(),
-        input[2..]
-            .iter()
-            .map(|line| (line[0..3].into(), (line[7..10].into(), line[12..15].into())))
-            .collect(),
-    )
+/// The `L`/`R` instructions, and the network where each node has an `L` and an `R` edge.
+pub type Network = (Vec<char>, Graph<char>);
+
+fn parse_input(input: &str) -> Result<Network> {
+    let first = parse::lines(input)
+        .next()
+        .unwrap_or(Line { index: 0, text: "" });
+    if let Some(i) = first.text.find(|c| c != 'L' && c != 'R') {
+        let c = first.text[i..].chars().next().unwrap();
+        return Err(first.error(&first.text[i..], format!("invalid instruction: '{c}'")));
+    }
+    let instructions: Vec<char> = first.text.chars().collect();
+    if instructions.is_empty() {
+        return Err(Error::parse(1, 1, "expected a list of instructions"));
+    }
+
+    let mut graph = Graph::new();
+    let mut references = vec![];
+    for line in parse::lines(input).skip(2) {
+        let (name, left, right) = line.record()?;
+        graph.add_edge(name, left, 'L');
+        graph.add_edge(name, right, 'R');
+        references.extend([(line, left), (line, right)]);
+    }
+
+    // Every node that is listed has edges, so those without any were only referred to.
+    match references
+        .into_iter()
+        .find(|&(_, node)| graph.edges(graph.id(node).unwrap()).is_empty())
+    {
+        Some((line, node)) => Err(line.error(node, "unknown node")),
+        None => Ok((instructions, graph)),
+    }
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1_1() {
+    fn problem1_1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/08-example1.txt")),
+            super::problem1(crate::lines_from_file("inputs/08-example1.txt")?)?,
             2
         );
+        Ok(())
     }
 
     #[test]
-    fn problem1_2() {
+    fn problem1_2() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/08-example2.txt")),
+            super::problem1(crate::lines_from_file("inputs/08-example2.txt")?)?,
             6
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/08-example3.txt")),
+            super::problem2(crate::lines_from_file("inputs/08-example3.txt")?)?,
             6
         );
+        Ok(())
+    }
+
+    #[test]
+    fn cycle() {
+        use crate::Solution;
+
+        let input = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(
+            super::Day08::solve1(input).unwrap_err().to_string(),
+            "The instructions lead from AAA around a cycle without ZZZ"
+        );
+        assert!(super::Day08::solve2(input).is_err());
+    }
+
+    #[test]
+    fn cancel() -> crate::Result<()> {
+        use crate::Solution;
+
+        let input = crate::input_from_file("inputs/08-example1.txt")?;
+        let token = crate::cancel::Token::with_budget(std::time::Duration::ZERO);
+        assert!(matches!(
+            token.scope(|| super::Day08::solve1(&input)),
+            Err(crate::Error::TimedOut(_))
+        ));
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/08.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/08.txt")?)?;
         println!("Solution for day 08 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/08.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/08.txt")?)?;
         println!("Solution for day 08 problem 2: {}", solution);
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+use crate::{parse, Error, Result, Solution};
+
 /// Day 9: Mirage Maintenance
 ///
 /// You ride the camel through the sandstorm and stop where the ghost's maps told you to stop.
@@ -18,7 +20,7 @@
 /// You pull out your handy Oasis And Sand Instability Sensor and analyze your surroundings.
 /// The OASIS produces a report of many values and how they are changing over time (your puzzle input).
 /// Each line in the report contains the history of a single value. For example:
-/// ```
+/// ```text
 /// 0 3 6 9 12 15
 /// 1 3 6 10 15 21
 /// 10 13 16 21 30 45
@@ -35,7 +37,7 @@
 /// Since these values aren't all zero, repeat the process: the values differ by 0 at each step,
 /// so the next sequence is 0 0 0 0. This means you have enough information to extrapolate the history!
 /// Visually, these sequences can be arranged like this:
-/// ```
+/// ```text
 /// 0   3   6   9  12  15
 ///   3   3   3   3   3
 ///     0   0   0   0
@@ -43,21 +45,21 @@
 /// To extrapolate, start by adding a new zero to the end of your list of zeroes;
 /// because the zeroes represent differences between the two values above them,
 /// this also means there is now a placeholder in every sequence above it:
-/// ```
+/// ```text
 /// 0   3   6   9  12  15   B
 ///   3   3   3   3   3   A
 ///     0   0   0   0   0
 /// ```
 /// You can then start filling in placeholders from the bottom up. A needs to be the result of increasing 3
 /// (the value to its left) by 0 (the value below it); this means A must be 3:
-/// ```
+/// ```text
 /// 0   3   6   9  12  15   B
 ///   3   3   3   3   3   3
 ///     0   0   0   0   0
 /// ```
 /// Finally, you can fill in B, which needs to be the result of increasing 15 (the value to its left)
 /// by 3 (the value below it), or 18:
-/// ```
+/// ```text
 /// 0   3   6   9  12  15  18
 ///   3   3   3   3   3   3
 ///     0   0   0   0   0
@@ -65,14 +67,14 @@
 /// So, the next value of the first history is 18.
 ///
 /// Finding all-zero differences for the second history requires an additional sequence:
-/// ```
+/// ```text
 /// 1   3   6  10  15  21
 ///   2   3   4   5   6
 ///     1   1   1   1
 ///       0   0   0
 /// ```
 /// Then, following the same process as before, work out the next value in each sequence from the bottom up:
-/// ```
+/// ```text
 /// 1   3   6  10  15  21  28
 ///   2   3   4   5   6   7
 ///     1   1   1   1   1
@@ -81,7 +83,7 @@
 /// So, the next value of the second history is 28.
 ///
 /// The third history requires even more sequences, but its next value can be found the same way:
-/// ```
+/// ```text
 /// 10  13  16  21  30  45  68
 ///    3   3   5   9  15  23
 ///      0   2   4   6   8
@@ -94,27 +96,35 @@
 ///
 /// Analyze your OASIS report and extrapolate the next value for each history.
 /// What is the sum of these extrapolated values?
-pub fn problem1(input: Vec<String>) -> isize {
-    input
-        .iter()
-        .map(|l| next_value(l.split(' ').filter_map(|s| s.parse().ok()).collect()))
-        .sum()
+pub fn problem1(input: Vec<String>) -> Result<isize> {
+    Day09::part1(&Day09::parse(&input.join("\n"))?)
 }
 
-fn next_value(current: Vec<isize>) -> isize {
-    let mut diffs = Vec::with_capacity(current.len() - 1);
-    let mut solved = true;
-    for i in 1..current.len() {
-        diffs.push(current[i] - current[i - 1]);
-        if diffs[i - 1] != diffs[0] {
-            solved = false;
+/// The next value is the sum of the last values of every row of the difference table, down to the first
+/// constant row. A constant history, including one of a single value, goes on with the same value.
+fn next_value(mut current: Vec<isize>) -> Result<isize> {
+    let mut lasts = vec![];
+    while let Some(&last) = current.last() {
+        lasts.push(last);
+        if current.iter().all(|&x| x == last) {
+            break;
         }
-    }
-    current.last().unwrap_or(&0)
-        + match solved {
-            true => diffs[0],
-            false => next_value(diffs),
+        for i in 1..current.len() {
+            current[i - 1] = current[i]
+                .checked_sub(current[i - 1])
+                .ok_or_else(overflow)?;
         }
+        current.pop();
+    }
+    lasts
+        .into_iter()
+        .rev()
+        .try_fold(0isize, |next, last| last.checked_add(next))
+        .ok_or_else(overflow)
+}
+
+fn overflow() -> Error {
+    Error::Invalid("The extrapolated value overflows".into())
 }
 
 /// Of course, it would be nice to have even more history included in your report.
@@ -126,12 +136,13 @@ fn next_value(current: Vec<isize>) -> isize {
 /// then fill in new first values for each previous sequence.
 ///
 /// In particular, here is what the third example history looks like when extrapolating back in time:
-/// ```
+/// ```text
 /// 5  10  13  16  21  30  45
 ///   5   3   3   5   9  15
 ///    -2   0   2   4   6
 ///       2   2   2   2
 ///         0   0   0
+/// ```
 /// Adding the new values on the left side of each sequence from bottom to top
 /// eventually reveals the new left-most history value: 5.
 ///
@@ -140,43 +151,110 @@ fn next_value(current: Vec<isize>) -> isize {
 ///
 /// Analyze your OASIS report again, this time extrapolating the previous value for each history.
 /// What is the sum of these extrapolated values?
-pub fn problem2(input: Vec<String>) -> isize {
-    input
-        .iter()
-        .map(|l| next_value(l.split(' ').filter_map(|c| c.parse().ok()).rev().collect()))
-        .sum()
+pub fn problem2(input: Vec<String>) -> Result<isize> {
+    Day09::part2(&Day09::parse(&input.join("\n"))?)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
+
+    type Input = Vec<Vec<isize>>;
+    type Output1 = isize;
+    type Output2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse::lines(input)
+            .map(|line| match line.numbers(line.text)? {
+                numbers if numbers.is_empty() => Err(line.error(line.text, "expected numbers")),
+                numbers => Ok(numbers),
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<isize> {
+        input.iter().try_fold(0isize, |sum, l| {
+            sum.checked_add(next_value(l.clone())?)
+                .ok_or_else(|| Error::Invalid("The sum overflows".into()))
+        })
+    }
+
+    fn part2(input: &Self::Input) -> Result<isize> {
+        input.iter().try_fold(0isize, |sum, l| {
+            sum.checked_add(next_value(l.iter().rev().copied().collect())?)
+                .ok_or_else(|| Error::Invalid("The sum overflows".into()))
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/09-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/09-example.txt")?)?,
             114
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
+    fn problem2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/09-example.txt")),
+            super::problem2(crate::lines_from_file("inputs/09-example.txt")?)?,
             2
         );
+        Ok(())
+    }
+
+    #[test]
+    fn single_value() -> crate::Result<()> {
+        use crate::Solution;
+
+        assert_eq!(super::Day09::solve1("-3 -5 -7\n -55\n")?, -64);
+        assert_eq!(super::Day09::solve2("-55")?, -55);
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() {
+        use crate::Solution;
+
+        let input = format!("0 {} {}", isize::MAX, isize::MIN);
+        assert!(super::Day09::solve1(&input).is_err());
+        assert!(super::Day09::solve1(&format!("0 1 {}", isize::MAX)).is_err());
+        let max = isize::MAX;
+        assert!(super::Day09::solve1(&format!("{max}\n{max}")).is_err());
+        assert!(super::Day09::solve2(&format!("{max}\n{max}")).is_err());
+    }
+
+    #[test]
+    fn long_line() -> crate::Result<()> {
+        use crate::Solution;
+
+        // The differences never become constant before the last row, 6000 rows down.
+        let line = "2 1 -1 -2 -1 1 ".repeat(1000);
+        assert_eq!(super::Day09::solve1(&line)?, 0);
+        assert_eq!(super::Day09::solve2(&line)?, 0);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/09.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/09.txt")?)?;
         println!("Solution for day 09 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/09.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/09.txt")?)?;
         println!("Solution for day 09 problem 2: {}", solution);
+        Ok(())
     }
 }
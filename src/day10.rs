@@ -1,3 +1,12 @@
+use std::collections::HashSet;
+
+use crate::{
+    geometry::Direction,
+    grid::{Coord, Grid},
+    render::{Colour, Render},
+    Error, Result, Solution,
+};
+
 /// Day 10: Pipe Maze
 ///
 /// You use the hang glider to ride the hot air from Desert Island all the way up to the floating metal
@@ -32,7 +41,7 @@
 /// that contains the animal is one large, continuous loop.
 ///
 /// For example, here is a square loop of pipe:
-/// ```
+/// ```text
 /// .....
 /// .F-7.
 /// .|.|.
@@ -40,7 +49,7 @@
 /// .....
 /// ```
 /// If the animal had entered this loop in the northwest corner, the sketch would instead look like this:
-/// ```
+/// ```text
 /// .....
 /// .S-7.
 /// .|.|.
@@ -52,11 +61,13 @@
 ///
 /// Unfortunately, there are also many pipes that aren't connected to the loop!
 /// This sketch shows the same loop as above:
-/// ```
+/// ```text
 /// -L|F7
+///
 /// 7S-7|
 /// L|7||
 /// -L-J|
+///
 /// L|-JF
 /// ```
 /// In the above diagram, you can still figure out which pipes form the main loop:
@@ -65,7 +76,7 @@
 /// two pipes connecting to it, and which is assumed to connect back to those two pipes).
 ///
 /// Here is a sketch that contains a slightly more complex main loop:
-/// ```
+/// ```text
 /// ..F7.
 /// .FJ|.
 /// SJ.L7
@@ -73,7 +84,7 @@
 /// LJ...
 /// ```
 /// Here's the same example sketch with the extra, non-main-loop pipe tiles also shown:
-/// ```
+/// ```text
 /// 7-F7-
 /// .FJ|7
 /// SJLL7
@@ -86,7 +97,7 @@
 /// the loop to reach from the starting point - regardless of which way around the loop the animal went.
 ///
 /// In the first example with the square loop:
-/// ```
+/// ```text
 /// .....
 /// .S-7.
 /// .|.|.
@@ -94,7 +105,7 @@
 /// .....
 /// ```
 /// You can count the distance each tile in the loop is from the starting point like this:
-/// ```
+/// ```text
 /// .....
 /// .012.
 /// .1.3.
@@ -104,7 +115,7 @@
 /// In this example, the farthest point from the start is 4 steps away.
 ///
 /// Here's the more complex loop again:
-/// ```
+/// ```text
 /// ..F7.
 /// .FJ|.
 /// SJ.L7
@@ -112,7 +123,7 @@
 /// LJ...
 /// ```
 /// Here are the distances for each tile on that loop:
-/// ```
+/// ```text
 /// ..45.
 /// .236.
 /// 01.78
@@ -123,60 +134,31 @@
 ///
 /// Find the single giant loop starting at S. How many steps along the loop does it take
 /// to get from the starting position to the point farthest from the starting position?
-pub fn problem1(input: Vec<String>) -> usize {
-    use Direction::*;
-
-    let input: Vec<Vec<char>> = input.into_iter().map(|s| s.chars().collect()).collect();
-    let (start_i, start_j) = start_coordinates(&input);
-
-    vec![U, D, L, R]
-        .iter()
-        .map(|d| path(&input, start_i, start_j, *d).len() / 2)
-        .max()
-        .unwrap_or(0)
+pub fn problem1(input: Vec<String>) -> Result<usize> {
+    Day10::part1(&Day10::parse(&input.join("\n"))?)
 }
 
-fn start_coordinates(input: &Vec<Vec<char>>) -> (usize, usize) {
+fn start_coordinates(input: &Grid<char>) -> Result<Coord> {
     input
-        .iter()
-        .enumerate()
-        .find_map(|(i, row)| {
-            row.iter()
-                .enumerate()
-                .find(|(_, &c)| c == 'S')
-                .map(|(j, _)| (i, j))
-        })
-        .unwrap()
+        .find(|&c| c == 'S')
+        .ok_or_else(|| Error::Invalid("There is no starting position S".into()))
 }
 
-fn path(
-    input: &Vec<Vec<char>>,
-    mut i: usize,
-    mut j: usize,
-    mut d: Direction,
-) -> Vec<(usize, usize)> {
+fn path(input: &Grid<char>, mut at: Coord, mut d: Direction) -> Vec<Coord> {
     use Direction::*;
     let mut path = Vec::new();
     loop {
-        match d {
-            U => match i > 0 {
-                true => i -= 1,
-                _ => return Vec::new(),
-            },
-            D => i += 1,
-            L => match j > 0 {
-                true => j -= 1,
-                _ => return Vec::new(),
-            },
-            R => j += 1,
-        }
-        path.push((i, j));
-        d = match (input[i][j], d) {
-            ('F', U) | ('L', D) => R,
-            ('7', U) | ('J', D) => L,
+        at = match input.step(at, d) {
+            Some(at) => at,
+            None => return Vec::new(),
+        };
+        path.push(at);
+        d = match (input[at], d) {
+            ('F', Up) | ('L', Down) => Right,
+            ('7', Up) | ('J', Down) => Left,
 
-            ('7', R) | ('F', L) => D,
-            ('J', R) | ('L', L) => U,
+            ('7', Right) | ('F', Left) => Down,
+            ('J', Right) | ('L', Left) => Up,
 
             ('|', _) | ('-', _) => d,
             ('S', _) | ('.', _) => return path,
@@ -185,20 +167,12 @@ fn path(
     }
 }
 
-#[derive(PartialEq, Clone, Copy, Debug)]
-enum Direction {
-    U,
-    D,
-    L,
-    R,
-}
-
 /// You quickly reach the farthest point of the loop, but the animal never emerges.
 /// Maybe its nest is within the area enclosed by the loop?
 ///
 /// To determine whether it's even worth taking the time to search for such a nest,
 /// you should calculate how many tiles are contained within the loop. For example:
-/// ```
+/// ```text
 /// ...........
 /// .S-------7.
 /// .|F-----7|.
@@ -211,7 +185,7 @@ enum Direction {
 /// ```
 /// The above loop encloses merely four tiles - the two pairs of . in the southwest and southeast (marked I below).
 /// The middle . tiles (marked O below) are not in the loop. Here is the same loop again with those regions marked:
-/// ```
+/// ```text
 /// ...........
 /// .S-------7.
 /// .|F-----7|.
@@ -224,7 +198,8 @@ enum Direction {
 /// ```
 /// In fact, there doesn't even need to be a full tile path to the outside for tiles to count as outside the loop
 /// - squeezing between pipes is also allowed! Here, I is still within the loop and O is still outside the loop:
-/// ```
+///
+/// ```text
 /// ..........
 /// .S------7.
 /// .|F----7|.
@@ -238,7 +213,7 @@ enum Direction {
 /// In both of the above examples, 4 tiles are enclosed by the loop.
 ///
 /// Here's a larger example:
-/// ```
+/// ```text
 /// .F----7F7F7F7F-7....
 /// .|F--7||||||||FJ....
 /// .||.FJ||||||||L7....
@@ -251,7 +226,7 @@ enum Direction {
 /// ....L---J.LJ.LJLJ...
 /// ```
 /// The above sketch has many random bits of ground, some of which are in the loop (I) and some of which are outside it (O):
-/// ```
+/// ```text
 /// OF----7F7F7F7F-7OOOO
 /// O|F--7||||||||FJOOOO
 /// O||OFJ||||||||L7OOOO
@@ -267,7 +242,7 @@ enum Direction {
 ///
 /// Any tile that isn't part of the main loop can count as being enclosed by the loop.
 /// Here's another example with many bits of junk pipe lying around that aren't connected to the main loop at all:
-/// ```
+/// ```text
 /// FF7FSF7F7F7F7F7F---7
 /// L|LJ||||||||||||F--J
 /// FL-7LJLJ||||||LJL-77
@@ -280,7 +255,7 @@ enum Direction {
 /// L7JLJL-JLJLJL--JLJ.L
 /// ```
 /// Here are just the tiles that are enclosed by the loop marked with I:
-/// ```
+/// ```text
 /// FF7FSF7F7F7F7F7F---7
 /// L|LJ||||||||||||F--J
 /// FL-7LJLJ||||||LJL-77
@@ -296,94 +271,241 @@ enum Direction {
 ///
 /// Figure out whether you have time to search for the nest by calculating the area within the loop.
 /// How many tiles are enclosed by the loop?
-pub fn problem2(input: Vec<String>) -> usize {
-    use Direction::*;
+pub fn problem2(input: Vec<String>) -> Result<usize> {
+    Day10::part2(&Day10::parse(&input.join("\n"))?)
+}
+
+pub struct Day10;
 
-    let input: Vec<Vec<char>> = input.into_iter().map(|s| s.chars().collect()).collect();
-    let (start_i, start_j) = start_coordinates(&input);
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
 
-    vec![U, D, L, R]
+    type Input = Grid<char>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let input = Grid::parse(input, "invalid tile", |c| {
+            "|-LJ7F.S".contains(c).then_some(c)
+        })?;
+        start_coordinates(&input)?;
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize> {
+        let start = start_coordinates(input)?;
+
+        Ok(Direction::ALL
+            .iter()
+            .map(|d| path(input, start, *d).len() / 2)
+            .max()
+            .unwrap_or(0))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize> {
+        Ok(inside(input, &main_loop(input)?).len())
+    }
+}
+
+/// The tiles of the loop that goes through S, from the one next to S to S itself.
+pub fn main_loop(input: &Grid<char>) -> Result<Vec<Coord>> {
+    let start = start_coordinates(input)?;
+    Ok(Direction::ALL
         .iter()
-        .map(|&d| (matches!(d, U | D), path(&input, start_i, start_j, d)))
-        .filter(|(_, p)| p.len() > 1)
-        .next()
-        .map(|(_vert, p)| {
-            (0..input.len())
-                .map(|i| {
-                    let mut inside = false;
-                    let mut res = 0;
-                    (0..input[0].len()).for_each(|j| {
-                        if !p.contains(&(i, j)) {
-                            res += inside as usize;
-                        } else if matches!(input[i][j], '|' | 'L' | 'J')
-                        // || _vert && input[i][j] == 'S'
-                        // Uncommenting the previous line makes the test fail, but the answer correct.
-                        // There's some weird edge case I'm missing.
-                        {
-                            inside = !inside;
-                        }
-                    });
-                    res
-                })
-                .sum()
+        .map(|&d| path(input, start, d))
+        .find(|p| p.len() > 1 && p.last() == Some(&start))
+        .unwrap_or_default())
+}
+
+/// The tiles enclosed by the loop, row by row.
+///
+/// Each row is scanned from the left, and every pipe of the loop that goes north flips between outside and inside.
+/// S stands for whichever pipe the loop needs there, so it goes north only when the loop leaves it northwards.
+pub fn inside(input: &Grid<char>, main_loop: &[Coord]) -> Vec<Coord> {
+    let s_north = match main_loop {
+        [first, .., before_s, s] => [first, before_s]
+            .into_iter()
+            .any(|&at| input.step(*s, Direction::Up) == Some(at)),
+        _ => false,
+    };
+    let main_loop: HashSet<Coord> = main_loop.iter().copied().collect();
+    let mut tiles = vec![];
+    for (i, row) in input.rows().enumerate() {
+        let mut inside = false;
+        for (j, &tile) in row.iter().enumerate() {
+            if !main_loop.contains(&(i, j)) {
+                if inside {
+                    tiles.push((i, j));
+                }
+            } else if matches!(tile, '|' | 'L' | 'J') || tile == 'S' && s_north {
+                inside = !inside;
+            }
+        }
+    }
+    tiles
+}
+
+/// Draws the map with the loop in green, the tiles it encloses in yellow, and S in red.
+/// Without colours, the enclosed tiles are drawn as `I`.
+pub fn render(input: &Grid<char>) -> Result<Render<'_, char>> {
+    let main_loop = main_loop(input)?;
+    Ok(Render::new(input)
+        .highlight(main_loop.iter().copied(), Colour::Green)
+        .mark(inside(input, &main_loop), Colour::Yellow, 'I')
+        .highlight([start_coordinates(input)?], Colour::Red))
+}
+
+/// The size of a tile in the SVG image, in pixels.
+const TILE: usize = 10;
+
+/// Draws the map as an SVG image: the loop as a green line through the middle of its tiles,
+/// the tiles it encloses shaded in yellow, and S as a red dot.
+pub fn svg(input: &Grid<char>) -> Result<String> {
+    let start = start_coordinates(input)?;
+    let main_loop = main_loop(input)?;
+    let centre = |(i, j): Coord| (j * TILE + TILE / 2, i * TILE + TILE / 2);
+
+    let (width, height) = (input.width() * TILE, input.height() * TILE);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+    svg += "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n";
+    for (i, j) in inside(input, &main_loop) {
+        svg += &format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{TILE}\" height=\"{TILE}\" fill=\"gold\"/>\n",
+            j * TILE,
+            i * TILE
+        );
+    }
+    let points: Vec<String> = std::iter::once(start)
+        .chain(main_loop)
+        .map(|at| {
+            let (x, y) = centre(at);
+            format!("{x},{y}")
         })
-        .unwrap_or(0)
+        .collect();
+    svg += &format!(
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"green\" stroke-width=\"2\"/>\n",
+        points.join(" ")
+    );
+    let (x, y) = centre(start);
+    svg += &format!(
+        "<circle cx=\"{x}\" cy=\"{y}\" r=\"{}\" fill=\"red\"/>\n",
+        TILE / 3
+    );
+    svg += "</svg>\n";
+    Ok(svg)
 }
 
 #[cfg(test)]
 mod test {
     #[test]
-    fn problem1_1() {
+    fn problem1_1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/10-example1.txt")),
+            super::problem1(crate::lines_from_file("inputs/10-example1.txt")?)?,
             4
         );
+        Ok(())
     }
 
     #[test]
-    fn problem1_2() {
+    fn problem1_2() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/10-example2.txt")),
+            super::problem1(crate::lines_from_file("inputs/10-example2.txt")?)?,
             8
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2_1() {
+    fn problem2_1() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/10-example3.txt")),
+            super::problem2(crate::lines_from_file("inputs/10-example3.txt")?)?,
             4
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2_2() {
+    fn problem2_2() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/10-example4.txt")),
+            super::problem2(crate::lines_from_file("inputs/10-example4.txt")?)?,
             8
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2_3() {
+    fn problem2_3() -> crate::Result<()> {
         assert_eq!(
-            super::problem2(crate::lines_from_file("inputs/10-example5.txt")),
+            super::problem2(crate::lines_from_file("inputs/10-example5.txt")?)?,
             10
         );
+        Ok(())
+    }
+
+    #[test]
+    fn svg() -> crate::Result<()> {
+        use crate::Solution;
+
+        let input = super::Day10::parse(&crate::input_from_file("inputs/10-example3.txt")?)?;
+        let svg = super::svg(&input)?;
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("fill=\"gold\"").count(), 4);
+        assert!(svg.contains("<polyline points=\"15,15 25,15 "));
+        assert!(svg.contains("<circle cx=\"15\" cy=\"15\""));
+        Ok(())
+    }
+
+    #[test]
+    fn loop_area() {
+        use crate::{
+            property::{self, ensure},
+            Solution,
+        };
+
+        // Pick's theorem: the area of the loop, from the shoelace formula, is the number of enclosed tiles
+        // plus half the number of loop tiles, minus one.
+        property::check(
+            |rng| property::input(10, rng).unwrap(),
+            |input| {
+                let grid = <super::Day10 as Solution>::parse(input)?;
+                let main_loop = super::main_loop(&grid)?;
+                ensure(main_loop.len() >= 4, || "the loop was not found".into())?;
+                let part1 = super::Day10::part1(&grid)?;
+                ensure(part1 == main_loop.len() / 2, || {
+                    format!("part 1 is {part1} for a loop of {}", main_loop.len())
+                })?;
+                let twice_area = main_loop
+                    .iter()
+                    .zip(main_loop.iter().cycle().skip(1))
+                    .map(|(&(i1, j1), &(i2, j2))| (j1 * i2) as i64 - (j2 * i1) as i64)
+                    .sum::<i64>()
+                    .unsigned_abs() as usize;
+                let enclosed = (twice_area + 2 - main_loop.len()) / 2;
+                let part2 = super::Day10::part2(&grid)?;
+                ensure(part2 == enclosed, || {
+                    format!("part 2 is {part2}, but Pick's theorem gives {enclosed}")
+                })
+            },
+        );
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/10.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/10.txt")?)?;
         println!("Solution for day 10 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/10.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/10.txt")?)?;
         println!("Solution for day 10 problem 2: {}", solution);
+        Ok(())
     }
 }
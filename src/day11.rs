@@ -1,3 +1,10 @@
+use crate::{
+    geometry::Point,
+    grid::{Coord, Grid},
+    render::{Colour, Render},
+    Error, Result, Solution,
+};
+
 /// Day 11: Cosmic Expansion
 ///
 /// You continue following signs for "Hot Springs" and eventually come across an observatory.
@@ -11,7 +18,7 @@
 ///
 /// The researcher has collected a bunch of data and compiled the data into a single giant image
 /// (your puzzle input). The image includes empty space (.) and galaxies (#). For example:
-/// ```
+/// ```text
 /// ...#......
 /// .......#..
 /// #.........
@@ -31,7 +38,7 @@
 /// the result is that any rows or columns that contain no galaxies should all actually be twice as big.
 ///
 /// In the above example, three columns and two rows contain no galaxies:
-/// ```
+/// ```text
 ///    v  v  v
 ///  ...#......
 ///  .......#..
@@ -46,7 +53,7 @@
 ///    ^  ^  ^
 /// ```
 /// These rows and columns need to be twice as big; the result of cosmic expansion therefore looks like this:
-/// ```
+/// ```text
 /// ....#........
 /// .........#...
 /// #............
@@ -62,7 +69,7 @@
 /// ```
 /// Equipped with this expanded universe, the shortest path between every pair of galaxies can be found.
 /// It can help to assign every galaxy a unique number:
-/// ```
+/// ```text
 /// ....1........
 /// .........2...
 /// 3............
@@ -75,6 +82,7 @@
 /// .............
 /// .........7...
 /// 8....9.......
+/// ```
 ///
 /// In these 9 galaxies, there are 36 pairs. Only count each pair once; order within the pair doesn't matter.
 /// For each pair, find any shortest path between the two galaxies using only steps
@@ -82,7 +90,7 @@
 /// (The shortest path between two galaxies is allowed to pass through another galaxy.)
 ///
 /// For example, here is one of the shortest paths between galaxies 5 and 9:
-/// ```
+/// ```text
 /// ....1........
 /// .........2...
 /// 3............
@@ -107,8 +115,8 @@
 ///
 /// Expand the universe, then find the length of the shortest path between every pair of galaxies.
 /// What is the sum of these lengths?
-pub fn problem1(input: Vec<String>) -> usize {
-    solve(input, 2)
+pub fn problem1(input: Vec<String>) -> Result<usize> {
+    Day11::part1(&Day11::parse(&input.join("\n"))?)
 }
 
 /// The galaxies are much older (and thus much farther apart) than the researcher initially estimated.
@@ -125,104 +133,168 @@ pub fn problem1(input: Vec<String>) -> usize {
 ///
 /// Starting with the same initial image, expand the universe according to these new rules,
 /// then find the length of the shortest path between every pair of galaxies. What is the sum of these lengths?
-pub fn problem2(input: Vec<String>) -> usize {
-    solve(input, 1_000_000)
+pub fn problem2(input: Vec<String>) -> Result<usize> {
+    Day11::part2(&Day11::parse(&input.join("\n"))?)
 }
 
-fn solve(input: Vec<String>, distance: usize) -> usize {
-    let universe: Vec<Vec<char>> = input
-        .into_iter()
-        .map(|line| line.chars().collect())
-        .collect();
-    let mut galaxies: Vec<(usize, usize)> = universe
-        .iter()
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+
+    type Input = Grid<char>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Grid::parse(input, "expected '.' or '#'", |c| {
+            matches!(c, '.' | '#').then_some(c)
+        })
+    }
+
+    fn part1(universe: &Self::Input) -> Result<usize> {
+        solve(universe, 2)
+    }
+
+    fn part2(universe: &Self::Input) -> Result<usize> {
+        solve(universe, 1_000_000)
+    }
+}
+
+/// The rows without any galaxy.
+pub fn empty_rows(universe: &Grid<char>) -> Vec<usize> {
+    universe
+        .rows()
         .enumerate()
-        .map(|(i, row)| {
-            row.iter()
-                .enumerate()
-                .filter_map(|(j, &c)| match c {
-                    '#' => Some((i, j)),
-                    _ => None,
-                })
-                .collect::<Vec<_>>()
+        .filter(|(_, row)| row.iter().all(|&c| c == '.'))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The columns without any galaxy.
+pub fn empty_columns(universe: &Grid<char>) -> Vec<usize> {
+    universe
+        .columns()
+        .enumerate()
+        .filter_map(|(j, mut column)| column.all(|&c| c == '.').then_some(j))
+        .collect()
+}
+
+/// Draws the universe with the galaxies in yellow, and the rows and columns that expand in blue.
+pub fn render(universe: &Grid<char>) -> Render<'_, char> {
+    Render::new(universe)
+        .rows(empty_rows(universe), Colour::Blue)
+        .columns(empty_columns(universe), Colour::Blue)
+        .highlight(universe.find_all(|&c| c == '#'), Colour::Yellow)
+}
+
+fn solve(universe: &Grid<char>, distance: usize) -> Result<usize> {
+    let mut galaxies: Vec<Coord> = universe.find_all(|&c| c == '#').collect();
+
+    empty_rows(universe).into_iter().rev().for_each(|row| {
+        galaxies.iter_mut().for_each(|(x, _)| {
+            if *x > row {
+                *x += distance - 1
+            }
         })
-        .flatten()
-        .collect();
-
-    let len = universe.len();
-    (0..len)
-        .filter(|&i| universe[i].iter().all(|&c| c == '.'))
-        .rev()
-        .for_each(|row| {
-            galaxies.iter_mut().for_each(|(x, _)| {
-                if *x > row {
-                    *x += distance - 1
-                }
-            })
-        });
-
-    (0..universe[0].len())
-        .filter(|&j| (0..len).all(|i| universe[i][j] == '.'))
-        .rev()
-        .for_each(|col| {
-            galaxies.iter_mut().for_each(|(_, y)| {
-                if *y > col {
-                    *y += distance - 1
-                }
-            })
-        });
+    });
+
+    empty_columns(universe).into_iter().rev().for_each(|col| {
+        galaxies.iter_mut().for_each(|(_, y)| {
+            if *y > col {
+                *y += distance - 1
+            }
+        })
+    });
 
     galaxies
         .iter()
         .enumerate()
-        .map(|(i, &(x1, y1))| {
-            galaxies
-                .iter()
-                .skip(i + 1)
-                .map(|&(x2, y2)| x1.abs_diff(x2) + y1.abs_diff(y2))
-                .sum::<usize>()
+        .flat_map(|(i, &a)| galaxies[i + 1..].iter().map(move |&b| (a, b)))
+        .try_fold(0usize, |sum, (a, b)| {
+            sum.checked_add(Point::from(a).manhattan(b.into())?)
         })
-        .sum()
+        .ok_or_else(|| Error::Invalid("The sum overflows".into()))
 }
 
 #[cfg(test)]
 mod test {
+    use crate::Solution;
+
     #[test]
-    fn problem1() {
+    fn problem1() -> crate::Result<()> {
         assert_eq!(
-            super::problem1(crate::lines_from_file("inputs/11-example.txt")),
+            super::problem1(crate::lines_from_file("inputs/11-example.txt")?)?,
             374
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2_1() {
+    fn problem2_1() -> crate::Result<()> {
         assert_eq!(
-            super::solve(crate::lines_from_file("inputs/11-example.txt"), 10),
+            super::solve(
+                &super::Day11::parse(&crate::input_from_file("inputs/11-example.txt")?)?,
+                10
+            )?,
             1030
         );
+        Ok(())
     }
 
     #[test]
-    fn problem2_2() {
+    fn problem2_2() -> crate::Result<()> {
         assert_eq!(
-            super::solve(crate::lines_from_file("inputs/11-example.txt"), 100),
+            super::solve(
+                &super::Day11::parse(&crate::input_from_file("inputs/11-example.txt")?)?,
+                100
+            )?,
             8410
         );
+        Ok(())
+    }
+
+    #[test]
+    fn expansion() {
+        use crate::property::{self, ensure};
+
+        // Every pair is a fixed number of empty rows and columns apart, so the sum grows linearly with the expansion.
+        property::check(
+            |rng| property::input(11, rng).unwrap(),
+            |input| {
+                let universe = super::Day11::parse(input)?;
+                let (once, twice) = (super::solve(&universe, 1)?, super::solve(&universe, 2)?);
+                let mut previous = once;
+                for distance in [2, 3, 10, 1_000_000] {
+                    let sum = super::solve(&universe, distance)?;
+                    ensure(sum >= previous, || {
+                        format!("{sum} at {distance} is less than {previous}")
+                    })?;
+                    ensure(sum == once + (distance - 1) * (twice - once), || {
+                        format!("{sum} at {distance} is not linear")
+                    })?;
+                    previous = sum;
+                }
+                Ok(())
+            },
+        );
     }
 }
 
 #[cfg(test)]
 mod solution {
     #[test]
-    fn problem1() {
-        let solution = super::problem1(crate::lines_from_file("inputs/11.txt"));
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/11.txt")?)?;
         println!("Solution for day 11 problem 1: {}", solution);
+        Ok(())
     }
 
     #[test]
-    fn problem2() {
-        let solution = super::problem2(crate::lines_from_file("inputs/11.txt"));
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/11.txt")?)?;
         println!("Solution for day 11 problem 2: {}", solution);
+        Ok(())
     }
 }
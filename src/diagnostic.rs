@@ -0,0 +1,122 @@
+use std::fmt;
+
+use crate::{Day, Error, Result, DAYS};
+
+/// A problem found in an input by [`crate::Solution::validate`].
+/// Both `line` and `column` start from 1; `text` is the offending line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub text: String,
+}
+
+impl Diagnostic {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Diagnostic {
+            line,
+            column,
+            message: message.into(),
+            text: String::new(),
+        }
+    }
+
+    /// The diagnostic of an error returned by [`crate::Solution::parse`]: errors without a position
+    /// point at the start of the input.
+    pub fn from_error(error: Error) -> Self {
+        match error {
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => Diagnostic::new(line, column, message),
+            e => Diagnostic::new(1, 1, e.to_string()),
+        }
+    }
+
+    /// Fills `text` with the offending line of the input.
+    pub fn source(mut self, input: &str) -> Self {
+        self.text = input
+            .lines()
+            .nth(self.line.saturating_sub(1))
+            .unwrap_or_default()
+            .to_string();
+        self
+    }
+}
+
+/// Shows the offending line under the message, with a caret under the column.
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let margin = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{margin} |")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{margin} | {}^",
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+/// Validates the input for the day, and refuses it if it has any diagnostic.
+/// When the input is only valid for another day, the error says so instead.
+pub fn check(day: &dyn Day, input: &str) -> Result<()> {
+    let diagnostics = day.validate(input);
+    if diagnostics.is_empty() {
+        return Ok(());
+    }
+
+    let others: Vec<u8> = DAYS
+        .iter()
+        .filter(|other| other.day() != day.day() && other.validate(input).is_empty())
+        .map(|other| other.day())
+        .collect();
+    match others[..] {
+        [other] => Err(Error::WrongDay {
+            day: day.day(),
+            other,
+        }),
+        _ => Err(Error::Diagnostics(diagnostics)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Diagnostic;
+
+    #[test]
+    fn display() {
+        let diagnostic = Diagnostic::new(2, 7, "invalid number").source("1 2\n10 20 x0\n");
+        assert_eq!(
+            diagnostic.to_string(),
+            "line 2, column 7: invalid number\n  |\n2 | 10 20 x0\n  |       ^"
+        );
+    }
+
+    #[test]
+    fn check() -> crate::Result<()> {
+        let day = |n| crate::day(n).unwrap();
+        let input = crate::input_from_file("inputs/05-example.txt")?;
+        assert!(super::check(day(5), &input).is_ok());
+        assert!(matches!(
+            super::check(day(6), &input),
+            Err(crate::Error::WrongDay { day: 6, other: 5 })
+        ));
+
+        let input = "seeds: 79 14\nTime: 7\n";
+        match super::check(day(6), input) {
+            Err(crate::Error::Diagnostics(diagnostics)) => {
+                assert_eq!(diagnostics[0].text, "seeds: 79 14")
+            }
+            result => panic!("{result:?}"),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use std::{fmt, io, path::PathBuf, time::Duration};
+
+use crate::diagnostic::Diagnostic;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input file does not exist.
+    MissingFile(PathBuf),
+    /// The input could not be read.
+    Io(io::Error),
+    /// Some text of the input does not match the format of the puzzle.
+    /// Both `line` and `column` start from 1.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input is well-formed line by line, but it cannot be solved as a whole.
+    Invalid(String),
+    /// Some setting, such as the session token, is missing or wrong.
+    Config(String),
+    /// The server could not be reached, or it replied with an unexpected status (0 if there was no reply).
+    Http { status: u16, message: String },
+    /// The solver panicked, with the given message.
+    Panicked(String),
+    /// The solver was cancelled once its time budget was spent.
+    TimedOut(Duration),
+    /// The input was refused by [`crate::diagnostic::check`].
+    Diagnostics(Vec<Diagnostic>),
+    /// The input of `day` is only valid for `other` day.
+    WrongDay { day: u8, other: u8 },
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Builds a parse error pointing at `token`, which must be a slice of `line`.
+    /// `index` is the position of the line in the input, starting from 0.
+    pub fn at(index: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&o| o <= line.len())
+            .unwrap_or(0);
+        let column = line[..offset].chars().count() + 1;
+        Error::parse(index + 1, column, message)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingFile(path) => write!(f, "no such file: {}", path.display()),
+            Error::Io(e) => write!(f, "could not read the input: {e}"),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "line {line}, column {column}: {message}"),
+            Error::Invalid(message) | Error::Config(message) => write!(f, "{message}"),
+            Error::Http { status: 0, message } => write!(f, "{message}"),
+            Error::Http { status, message } => write!(f, "HTTP {status}: {message}"),
+            Error::Panicked(message) => write!(f, "panicked: {message}"),
+            Error::TimedOut(budget) => write!(f, "timed out after {} s", budget.as_secs_f64()),
+            Error::Diagnostics(diagnostics) => {
+                let diagnostics: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+                write!(f, "{}", diagnostics.join("\n"))
+            }
+            Error::WrongDay { day, other } => write!(
+                f,
+                "this looks like the input of day {other}, not of day {day}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Error;
+
+    #[test]
+    fn at() {
+        let line = "Game 1: 3 blue, 4 red";
+        let e = Error::at(4, line, &line[16..], "bad");
+        assert!(matches!(
+            e,
+            Error::Parse {
+                line: 5,
+                column: 17,
+                ..
+            }
+        ));
+        assert_eq!(e.to_string(), "line 5, column 17: bad");
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(matches!(
+            crate::lines_from_file("inputs/00.txt"),
+            Err(Error::MissingFile(path)) if path.ends_with("00.txt")
+        ));
+    }
+}
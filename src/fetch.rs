@@ -0,0 +1,139 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    http::{Http, Request},
+    Error, Result,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com/2023";
+
+/// The file where the session token can be stored, if the `AOC_SESSION` variable is not set.
+pub const SESSION_FILE: &str = ".aoc-session";
+
+/// Reads the session token from the `AOC_SESSION` environment variable, or from [`SESSION_FILE`].
+/// It is the value of the `session` cookie of adventofcode.com once logged in.
+pub fn session() -> Result<String> {
+    let token = match std::env::var("AOC_SESSION") {
+        Ok(token) => token,
+        Err(_) => fs::read_to_string(SESSION_FILE).map_err(|_| missing_session())?,
+    };
+    match token.trim() {
+        "" => Err(Error::Config("The session token is empty".into())),
+        token => Ok(token.to_string()),
+    }
+}
+
+fn missing_session() -> Error {
+    Error::Config(format!(
+        "No session token: set AOC_SESSION or write it to {SESSION_FILE}"
+    ))
+}
+
+/// Downloads the puzzle inputs into `inputs/NN.txt`, unless they are already there.
+pub struct Fetcher<H> {
+    http: H,
+    session: Option<String>,
+    base_url: String,
+    dir: PathBuf,
+}
+
+impl<H: Http> Fetcher<H> {
+    pub fn new(http: H, session: Option<String>) -> Self {
+        Fetcher {
+            http,
+            session,
+            base_url: BASE_URL.into(),
+            dir: "inputs".into(),
+        }
+    }
+
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    pub fn dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.dir = dir.as_ref().into();
+        self
+    }
+
+    /// Where the input of the day is cached.
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("{day:02}.txt"))
+    }
+
+    /// Returns the path of the input of the day, downloading it only if it is not cached yet.
+    /// An empty file, such as one left by an older `aoc new`, does not count as cached.
+    pub fn fetch(&self, day: u8) -> Result<PathBuf> {
+        let path = self.path(day);
+        if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok(path);
+        }
+
+        let session = self.session.as_deref().ok_or_else(missing_session)?;
+        let request = Request::get(format!("{}/day/{day}/input", self.base_url))
+            .header("Cookie", &format!("session={session}"));
+        let response = self.http.send(&request)?;
+        if response.status != 200 {
+            return Err(Error::Http {
+                status: response.status,
+                message: response.body.trim().to_string(),
+            });
+        }
+
+        // Write to a temporary file first, so that an interrupted download is never taken for a cached input.
+        fs::create_dir_all(&self.dir)?;
+        let partial = path.with_extension("txt.part");
+        fs::write(&partial, response.body)?;
+        fs::rename(partial, &path)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http::TcpHttp;
+
+    #[test]
+    fn fetch() -> crate::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (url, server) = crate::http::stub::serve(vec![
+            (200, "1abc2\n"),
+            (400, "Puzzle inputs differ by user.\n"),
+        ]);
+        let fetcher = super::Fetcher::new(TcpHttp, Some("cookie".into()))
+            .base_url(&url)
+            .dir(&dir);
+
+        let path = fetcher.fetch(1)?;
+        assert_eq!(path, dir.join("01.txt"));
+        assert_eq!(std::fs::read_to_string(&path)?, "1abc2\n");
+        // The second time the cached file is returned, without any request.
+        assert_eq!(fetcher.fetch(1)?, path);
+        assert!(matches!(
+            fetcher.fetch(2),
+            Err(crate::Error::Http { status: 400, .. })
+        ));
+        assert!(!dir.join("02.txt").exists());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=cookie\r\n"));
+        assert!(requests[0].contains("User-Agent: https://github.com/muraca/adventofcode2023"));
+        assert!(requests[1].starts_with("GET /day/2/input HTTP/1.1\r\n"));
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn no_session() {
+        let fetcher = super::Fetcher::new(TcpHttp, None).dir("inputs");
+        assert!(fetcher.fetch(1).is_ok());
+        assert!(matches!(fetcher.fetch(25), Err(crate::Error::Config(_))));
+    }
+}
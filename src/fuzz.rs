@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use crate::{cancel::Token, diagnostic, property::Rng, Day};
+
+/// How long each part may run on one input before [`target`] gives up on it.
+const BUDGET: Duration = Duration::from_secs(1);
+
+/// The fuzz entry point of a day: checks, parses and solves both parts of arbitrary bytes.
+///
+/// Refusing the input or failing to solve it is fine; only a panic, or a solver that does not give up
+/// once its time budget is spent, is a bug.
+pub fn target(day: &dyn Day, data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    if diagnostic::check(day, input).is_err() {
+        return;
+    }
+    let Ok(parsed) = day.parse(input) else {
+        return;
+    };
+    for part in [1, 2] {
+        let _ = Token::with_budget(BUDGET).scope(|| day.solve(parsed.as_ref(), part));
+    }
+}
+
+/// Tokens that matter to the parsers, so that mutations reach past their first checks.
+const TOKENS: [&str; 22] = [
+    "\n",
+    "\n\n",
+    " ",
+    ":",
+    ";",
+    ",",
+    "|",
+    "=",
+    "(",
+    ")",
+    "-",
+    "0",
+    "9",
+    "L",
+    "R",
+    "Z",
+    "J",
+    "S",
+    "#",
+    "4294967295",
+    "9223372036854775807",
+    "99999999999999999999",
+];
+
+/// Changes `input` a little: removes, repeats or overwrites a part of it, or inserts a token.
+/// Once in a while, the part is repeated hundreds of times, which makes much longer lines than the generators do.
+pub fn mutate(rng: &mut Rng, input: &mut Vec<u8>) {
+    for _ in 0..rng.between(1, 4) {
+        let at = rng.below(input.len() + 1);
+        let end = (at + rng.between(1, 8) as usize).min(input.len());
+        match rng.below(5) {
+            0 => {
+                input.drain(at..end);
+            }
+            1 => {
+                let times = match rng.one_in(8) {
+                    true => rng.between(100, 2000) as usize,
+                    false => 1,
+                };
+                let part = input[at..end].repeat(times);
+                input.splice(at..at, part);
+            }
+            2 if at < input.len() => input[at] = rng.next_u64() as u8,
+            3 => {
+                let i = rng.below(input.len() + 1);
+                input.truncate(i);
+            }
+            _ => {
+                let token = rng.pick(&TOKENS).bytes();
+                input.splice(at..at, token);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use crate::{
+        property::{self, Rng},
+        Error, DAYS,
+    };
+
+    /// Runs every target on mutated versions of the generated inputs, as a deterministic loop
+    /// whose seed and number of cases come from [`property::check`].
+    #[test]
+    fn targets() {
+        for day in DAYS {
+            if property::input(day.day(), &mut Rng::new(0)).is_none() {
+                continue;
+            }
+            property::check(
+                |rng| {
+                    let mut input = property::input(day.day(), rng).unwrap().into_bytes();
+                    super::mutate(rng, &mut input);
+                    String::from_utf8_lossy(&input).into_owned()
+                },
+                |input| {
+                    panic::catch_unwind(AssertUnwindSafe(|| super::target(day, input.as_bytes())))
+                        .map_err(|_| Error::Invalid(format!("day {} panicked", day.day())))
+                },
+            );
+        }
+    }
+}
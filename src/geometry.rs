@@ -0,0 +1,201 @@
+use std::ops::{Add, Sub};
+
+use crate::grid::Coord;
+
+/// A point on a plane where `y` grows downwards, like the rows of the inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// The (row, column) of a grid is the point (column, row).
+impl From<Coord> for Point<usize> {
+    fn from((row, column): Coord) -> Self {
+        Point::new(column, row)
+    }
+}
+
+impl From<Point<usize>> for Coord {
+    fn from(p: Point<usize>) -> Self {
+        (p.y, p.x)
+    }
+}
+
+/// The points in a rectangle, borders included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+macro_rules! distances {
+    ($($t:ty => $u:ty),*) => {$(
+        impl Point<$t> {
+            /// The number of steps from `self` to `other` when moving up, down, left or right.
+            /// `None` when it does not fit in the unsigned type.
+            pub fn manhattan(self, other: Self) -> Option<$u> {
+                self.x.abs_diff(other.x).checked_add(self.y.abs_diff(other.y))
+            }
+
+            /// The number of steps from `self` to `other` when moving diagonally too.
+            /// Unlike [`Point::manhattan`], it always fits in the unsigned type.
+            pub fn chebyshev(self, other: Self) -> $u {
+                self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+            }
+        }
+
+        impl Bounds<$t> {
+            /// The smallest rectangle holding all the points, if there is any.
+            pub fn of(points: impl IntoIterator<Item = Point<$t>>) -> Option<Self> {
+                let mut points = points.into_iter();
+                let first = points.next()?;
+                Some(points.fold(Bounds { min: first, max: first }, |b, p| b.extend(p)))
+            }
+
+            /// The smallest rectangle holding `self` and `p`.
+            pub fn extend(self, p: Point<$t>) -> Self {
+                Bounds {
+                    min: Point::new(self.min.x.min(p.x), self.min.y.min(p.y)),
+                    max: Point::new(self.max.x.max(p.x), self.max.y.max(p.y)),
+                }
+            }
+
+            /// The rectangle grown by `n` on every side, without overflowing.
+            pub fn grow(self, n: $t) -> Self {
+                Bounds {
+                    min: Point::new(self.min.x.saturating_sub(n), self.min.y.saturating_sub(n)),
+                    max: Point::new(self.max.x.saturating_add(n), self.max.y.saturating_add(n)),
+                }
+            }
+
+            pub fn contains(&self, p: Point<$t>) -> bool {
+                (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+            }
+
+            /// The number of columns, borders included; `None` when it does not fit in the unsigned type.
+            pub fn width(&self) -> Option<$u> {
+                self.min.x.abs_diff(self.max.x).checked_add(1)
+            }
+
+            /// The number of rows, borders included; `None` when it does not fit in the unsigned type.
+            pub fn height(&self) -> Option<$u> {
+                self.min.y.abs_diff(self.max.y).checked_add(1)
+            }
+        }
+    )*};
+}
+
+distances!(i32 => u32, i64 => u64, isize => usize, u32 => u32, u64 => u64, usize => usize);
+
+/// A direction on the plane of [`Point`], where up is towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise, starting from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// A quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// A quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The step of one unit in this direction.
+    pub fn delta(self) -> Point<isize> {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bounds, Direction, Point};
+
+    #[test]
+    fn distances() {
+        let (a, b) = (Point::new(-1i64, 2), Point::new(3, -4));
+        assert_eq!(a.manhattan(b), Some(10));
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(Point::new(1usize, 5).manhattan(Point::new(4, 0)), Some(8));
+        let (min, max) = (
+            Point::new(i64::MIN, i64::MIN),
+            Point::new(i64::MAX, i64::MAX),
+        );
+        assert_eq!(min.manhattan(max), None);
+        assert_eq!(min.chebyshev(max), u64::MAX);
+        assert_eq!(
+            Point::new(0u32, 0).manhattan(Point::new(u32::MAX, 0)),
+            Some(u32::MAX)
+        );
+        assert_eq!(a + b - b, a);
+    }
+
+    #[test]
+    fn bounds() {
+        let bounds =
+            Bounds::<usize>::of([Point::new(2, 0), Point::new(0, 3), Point::new(1, 1)]).unwrap();
+        assert_eq!((bounds.width(), bounds.height()), (Some(3), Some(4)));
+        assert!(bounds.contains(Point::new(1, 3)));
+        assert!(!bounds.contains(Point::new(3, 3)));
+        assert_eq!(bounds.grow(1).min, Point::new(0, 0));
+        assert!(Bounds::<i64>::of([]).is_none());
+        let whole = Bounds::<i64>::of([Point::new(i64::MIN, 0), Point::new(i64::MAX, 0)]).unwrap();
+        assert_eq!((whole.width(), whole.height()), (None, Some(1)));
+    }
+
+    #[test]
+    fn directions() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.delta() + d.opposite().delta(), Point::new(0, 0));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    }
+}
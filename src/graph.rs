@@ -0,0 +1,294 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    io::{self, Write},
+};
+
+/// The index of a node in a [`Graph`].
+pub type NodeId = usize;
+
+/// Gives each distinct name an id, counting from 0 in order of first appearance.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    ids: HashMap<String, NodeId>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.into(), id);
+        self.names.push(name.into());
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A directed graph of named nodes, whose edges carry a label such as the instruction that follows them.
+#[derive(Debug, Clone)]
+pub struct Graph<E = ()> {
+    names: Interner,
+    edges: Vec<Vec<(NodeId, E)>>,
+}
+
+impl<E> Default for Graph<E> {
+    fn default() -> Self {
+        Graph {
+            names: Interner::default(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<E> Graph<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the node with that name, which is added if it is not there yet.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        let id = self.names.intern(name);
+        if id == self.edges.len() {
+            self.edges.push(vec![]);
+        }
+        id
+    }
+
+    /// Adds an edge between the named nodes, adding them too if needed, and returns their ids.
+    pub fn add_edge(&mut self, from: &str, to: &str, label: E) -> (NodeId, NodeId) {
+        let (from, to) = (self.node(from), self.node(to));
+        self.edges[from].push((to, label));
+        (from, to)
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.names.get(name)
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        self.names.name(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        0..self.len()
+    }
+
+    /// The edges leaving `id`, as their target and label, in the order they were added.
+    pub fn edges(&self, id: NodeId) -> &[(NodeId, E)] {
+        &self.edges[id]
+    }
+
+    /// The target of the first edge leaving `id` with that label.
+    pub fn follow(&self, id: NodeId, label: &E) -> Option<NodeId>
+    where
+        E: PartialEq,
+    {
+        self.edges[id]
+            .iter()
+            .find(|(_, l)| l == label)
+            .map(|&(to, _)| to)
+    }
+
+    /// The number of edges from `start` to each node, or `None` for the nodes it cannot reach.
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.len()];
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+        while let Some(id) = queue.pop_front() {
+            let d = distances[id].map(|d| d + 1);
+            for &(to, _) in &self.edges[id] {
+                if distances[to].is_none() {
+                    distances[to] = d;
+                    queue.push_back(to);
+                }
+            }
+        }
+        distances
+    }
+
+    /// The nodes reachable from `start`, in depth-first preorder.
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut seen = vec![false; self.len()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+        while let Some(id) = stack.pop() {
+            if std::mem::replace(&mut seen[id], true) {
+                continue;
+            }
+            order.push(id);
+            stack.extend(self.edges[id].iter().rev().map(|&(to, _)| to));
+        }
+        order
+    }
+
+    /// Whether each node can be reached from `start`, which always reaches itself.
+    pub fn reachable(&self, start: NodeId) -> Vec<bool> {
+        self.bfs(start).iter().map(Option::is_some).collect()
+    }
+
+    /// The strongly connected components, each listed before the components it has edges to.
+    pub fn scc(&self) -> Vec<Vec<NodeId>> {
+        // Tarjan's algorithm, with an explicit stack of (node, next edge) instead of recursion.
+        let n = self.len();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = vec![];
+        let mut components = vec![];
+        let mut next = 0;
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            while let Some((id, edge)) = calls.pop() {
+                if edge == 0 {
+                    index[id] = next;
+                    low[id] = next;
+                    next += 1;
+                    stack.push(id);
+                    on_stack[id] = true;
+                }
+                if let Some(&(to, _)) = self.edges[id].get(edge) {
+                    calls.push((id, edge + 1));
+                    if index[to] == usize::MAX {
+                        calls.push((to, 0));
+                    } else if on_stack[to] {
+                        low[id] = low[id].min(index[to]);
+                    }
+                    continue;
+                }
+                if low[id] == index[id] {
+                    let mut component = vec![];
+                    loop {
+                        let top = stack.pop().unwrap();
+                        on_stack[top] = false;
+                        component.push(top);
+                        if top == id {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent] = low[parent].min(low[id]);
+                }
+            }
+        }
+
+        components.reverse();
+        components
+    }
+
+    /// Writes the graph in the DOT language of Graphviz, with the labels on the edges.
+    pub fn write_dot(&self, mut w: impl Write) -> io::Result<()>
+    where
+        E: Display,
+    {
+        writeln!(w, "digraph {{")?;
+        for from in self.nodes() {
+            writeln!(w, "    {:?};", self.name(from))?;
+            for (to, label) in &self.edges[from] {
+                writeln!(
+                    w,
+                    "    {:?} -> {:?} [label={:?}];",
+                    self.name(from),
+                    self.name(*to),
+                    label.to_string()
+                )?;
+            }
+        }
+        writeln!(w, "}}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Graph;
+
+    fn graph() -> Graph<char> {
+        let mut graph = Graph::new();
+        for (from, to, label) in [
+            ("a", "b", 'x'),
+            ("b", "c", 'x'),
+            ("c", "a", 'y'),
+            ("c", "d", 'x'),
+            ("d", "e", 'x'),
+            ("e", "d", 'x'),
+        ] {
+            graph.add_edge(from, to, label);
+        }
+        graph.node("f");
+        graph
+    }
+
+    #[test]
+    fn interning() {
+        let graph = graph();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(graph.id("c"), Some(2));
+        assert_eq!(graph.name(5), "f");
+        assert_eq!(graph.follow(2, &'y'), Some(0));
+        assert_eq!(graph.follow(2, &'z'), None);
+    }
+
+    #[test]
+    fn search() {
+        let graph = graph();
+        assert_eq!(
+            graph.bfs(0),
+            vec![Some(0), Some(1), Some(2), Some(3), Some(4), None]
+        );
+        assert_eq!(graph.dfs(2), vec![2, 0, 1, 3, 4]);
+        assert_eq!(
+            graph.reachable(3),
+            vec![false, false, false, true, true, false]
+        );
+    }
+
+    #[test]
+    fn scc() {
+        let mut components = graph().scc();
+        components.iter_mut().for_each(|c| c.sort());
+        assert_eq!(components, vec![vec![5], vec![0, 1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn dot() {
+        let mut graph = Graph::new();
+        graph.add_edge("AAA", "BBB", 'L');
+        let mut out = vec![];
+        graph.write_dot(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "digraph {\n    \"AAA\";\n    \"AAA\" -> \"BBB\" [label=\"L\"];\n    \"BBB\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    geometry::{Direction, Point},
+    Error, Result,
+};
+
+/// A position in a [`Grid`], as (row, column) from the top left corner.
+pub type Coord = (usize, usize);
+
+/// A rectangle of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells, row by row. There must be `width * height` of them.
+    pub fn new(width: usize, cells: Vec<T>) -> Self {
+        let height = match width {
+            0 => 0,
+            _ => cells.len() / width,
+        };
+        assert_eq!(width * height, cells.len(), "the grid is not a rectangle");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell from each character of the lines of `input`, which must all have the same length.
+    /// Characters for which `cell` returns `None` are reported with `message`.
+    pub fn parse(input: &str, message: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self> {
+        let width = input.lines().next().map_or(0, |l| l.chars().count());
+        let mut cells = vec![];
+        for (i, line) in input.lines().enumerate() {
+            let mut len = 0;
+            for (j, c) in line.chars().enumerate() {
+                cells.push(cell(c).ok_or_else(|| Error::parse(i + 1, j + 1, message))?);
+                len += 1;
+            }
+            if len != width {
+                return Err(Error::parse(
+                    i + 1,
+                    len.min(width) + 1,
+                    format!("expected {width} columns"),
+                ));
+            }
+        }
+        Ok(Grid::new(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (i, j): Coord) -> bool {
+        i < self.height && j < self.width
+    }
+
+    pub fn get(&self, at: Coord) -> Option<&T> {
+        self.contains(at)
+            .then(|| &self.cells[at.0 * self.width + at.1])
+    }
+
+    pub fn get_mut(&mut self, at: Coord) -> Option<&mut T> {
+        match self.contains(at) {
+            true => Some(&mut self.cells[at.0 * self.width + at.1]),
+            false => None,
+        }
+    }
+
+    /// The coordinate `(di, dj)` away from `at`, if it is inside the grid.
+    pub fn offset(&self, (i, j): Coord, (di, dj): (isize, isize)) -> Option<Coord> {
+        let at = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+        self.contains(at).then_some(at)
+    }
+
+    /// The coordinate next to `at` in direction `d`, if it is inside the grid.
+    pub fn step(&self, at: Coord, d: Direction) -> Option<Coord> {
+        let Point { x, y } = d.delta();
+        self.offset(at, (y, x))
+    }
+
+    /// The neighbours that are inside the grid, clockwise from the one above.
+    pub fn neighbours4(&self, at: Coord) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(at, d))
+    }
+
+    /// The neighbours that are inside the grid, diagonals included.
+    pub fn neighbours8(&self, at: Coord) -> impl Iterator<Item = Coord> + '_ {
+        (-1..=1)
+            .flat_map(|di| (-1..=1).map(move |dj| (di, dj)))
+            .filter(|&d| d != (0, 0))
+            .filter_map(move |d| self.offset(at, d))
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(j).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|j| self.column(j))
+    }
+
+    /// Every cell along with its coordinate, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(n, cell)| ((n / self.width, n % self.width), cell))
+    }
+
+    /// The coordinate of the first cell, row by row, that matches.
+    pub fn find(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.iter().find(|(_, c)| matches(c)).map(|(at, _)| at)
+    }
+
+    /// The coordinates of every cell that matches, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        mut matches: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Coord> + 'a {
+        self.iter()
+            .filter(move |(_, c)| matches(c))
+            .map(|(at, _)| at)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate(&self) -> Self {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|j| (0..self.height).rev().map(move |i| self[(i, j)].clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, at: Coord) -> &T {
+        self.get(at).expect("the coordinate is outside the grid")
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, at: Coord) -> &mut T {
+        self.get_mut(at)
+            .expect("the coordinate is outside the grid")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Grid;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, "invalid", Some).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let g = grid("abc\ndef\n");
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(1, 2)], 'f');
+        assert_eq!(g.get((2, 0)), None);
+        assert!(matches!(
+            Grid::parse("ab\nc\n", "invalid", Some),
+            Err(crate::Error::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Grid::parse("ab\nc#\n", "invalid", |c| (c != '#').then_some(c)),
+            Err(crate::Error::Parse {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = grid("abc\ndef\nghi");
+        assert_eq!(
+            g.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(g.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            g.neighbours8((2, 2)).map(|at| g[at]).collect::<String>(),
+            "efh"
+        );
+        assert_eq!(g.offset((0, 2), (0, 1)), None);
+    }
+
+    #[test]
+    fn transform() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose(), grid("ad\nbe\ncf"));
+        assert_eq!(g.rotate(), grid("da\neb\nfc"));
+        assert_eq!(g.rotate().rotate().rotate().rotate(), g);
+        assert_eq!(g.column(1).collect::<String>(), "be");
+        assert_eq!(g.rows().map(|r| r.len()).sum::<usize>(), 6);
+        assert_eq!(g.find(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(g.find_all(|&c| c > 'b').count(), 4);
+    }
+}
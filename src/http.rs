@@ -0,0 +1,321 @@
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+};
+
+use crate::{Error, Result};
+
+/// Sent along with every request, as asked by the Advent of Code maintainers.
+pub const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_REPOSITORY"),
+    " (",
+    env!("CARGO_PKG_NAME"),
+    " ",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Request {
+    pub fn get(url: impl Into<String>) -> Self {
+        Request {
+            method: "GET",
+            url: url.into(),
+            headers: vec![("User-Agent".into(), USER_AGENT.into())],
+            body: None,
+        }
+    }
+
+    /// A POST request with an `application/x-www-form-urlencoded` body.
+    pub fn post(url: impl Into<String>, form: &[(&str, &str)]) -> Self {
+        let body = form
+            .iter()
+            .map(|(k, v)| format!("{}={}", url_encode(k), url_encode(v)))
+            .collect::<Vec<_>>()
+            .join("&");
+        Request {
+            method: "POST",
+            body: Some(body),
+            ..Request::get(url)
+        }
+        .header("Content-Type", "application/x-www-form-urlencoded")
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// The way requests reach the server, so that it can be replaced in tests.
+pub trait Http {
+    fn send(&self, request: &Request) -> Result<Response>;
+}
+
+/// Plain HTTP/1.1 over a [`TcpStream`]: it does not support TLS, so it only works with `http://` urls,
+/// such as a local server.
+pub struct TcpHttp;
+
+impl Http for TcpHttp {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let rest = request
+            .url
+            .strip_prefix("http://")
+            .ok_or_else(|| Error::Config(format!("Unsupported url: {}", request.url)))?;
+        let (host, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/"),
+        };
+
+        let mut head = format!(
+            "{} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n",
+            request.method
+        );
+        for (name, value) in &request.headers {
+            head += &format!("{name}: {value}\r\n");
+        }
+        let body = request.body.as_deref().unwrap_or_default();
+        if request.body.is_some() {
+            head += &format!("Content-Length: {}\r\n", body.len());
+        }
+
+        let mut stream = TcpStream::connect(host)?;
+        stream.write_all(format!("{head}\r\n{body}").as_bytes())?;
+        let mut response = vec![];
+        stream.read_to_end(&mut response)?;
+        parse_response(&String::from_utf8_lossy(&response))
+    }
+}
+
+fn parse_response(response: &str) -> Result<Response> {
+    let malformed = || Error::Http {
+        status: 0,
+        message: "Malformed response".into(),
+    };
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(malformed)?;
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(malformed)?;
+    let chunked = lines.any(|l| {
+        l.split_once(':').is_some_and(|(k, v)| {
+            k.eq_ignore_ascii_case("transfer-encoding") && v.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+
+    Ok(Response {
+        status,
+        body: match chunked {
+            true => dechunk(body).ok_or_else(malformed)?,
+            false => body.to_string(),
+        },
+    })
+}
+
+fn dechunk(mut body: &str) -> Option<String> {
+    let mut out = String::new();
+    loop {
+        let (size, rest) = body.split_once("\r\n")?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        if size == 0 {
+            return Some(out);
+        }
+        out += rest.get(..size)?;
+        body = rest.get(size..)?.strip_prefix("\r\n")?;
+    }
+}
+
+/// HTTPS through the `curl` command, which must be installed.
+pub struct Curl;
+
+impl Curl {
+    /// The command sending `request`, along with the configuration to write on its standard input.
+    /// The headers go in the configuration rather than in the arguments, where any local user
+    /// could read the session cookie.
+    fn command(request: &Request) -> (Command, String) {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--request", request.method])
+            .args(["--write-out", "\n%{http_code}", "--config", "-"]);
+        if let Some(body) = &request.body {
+            command.arg("--data-raw").arg(body);
+        }
+        command.arg(&request.url);
+
+        let config = request
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let header = format!("{name}: {value}")
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"");
+                format!("header = \"{header}\"\n")
+            })
+            .collect();
+        (command, config)
+    }
+}
+
+impl Http for Curl {
+    fn send(&self, request: &Request) -> Result<Response> {
+        let (mut command, config) = Curl::command(request);
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(config.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(Error::Http {
+                status: 0,
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        let output = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = output.rsplit_once('\n').unwrap_or(("", &output));
+        Ok(Response {
+            status: status.trim().parse().unwrap_or_default(),
+            body: body.to_string(),
+        })
+    }
+}
+
+/// Picks [`TcpHttp`] for `http://` urls and [`Curl`] for anything else.
+pub struct Auto;
+
+impl Http for Auto {
+    fn send(&self, request: &Request) -> Result<Response> {
+        match request.url.starts_with("http://") {
+            true => TcpHttp.send(request),
+            false => Curl.send(request),
+        }
+    }
+}
+
+/// A local server replying to each connection with the next of the given responses.
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Returns the base url of the server, and a handle yielding the requests it received.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut request = vec![];
+                    let mut buf = [0; 4096];
+                    // Read the head, then as many bytes as the Content-Length says.
+                    let request = loop {
+                        let n = stream.read(&mut buf).unwrap();
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request).to_string();
+                        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                            let len = head
+                                .lines()
+                                .find_map(|l| l.strip_prefix("Content-Length: "))
+                                .map_or(0, |l| l.parse().unwrap());
+                            if body.len() >= len || n == 0 {
+                                break text;
+                            }
+                        }
+                    };
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Http, Request};
+
+    #[test]
+    fn tcp() -> crate::Result<()> {
+        let (url, server) = super::stub::serve(vec![(200, "hello"), (404, "")]);
+
+        let response = super::TcpHttp.send(&Request::post(
+            format!("{url}/2023/day/1/answer"),
+            &[("level", "1"), ("answer", "a b&c")],
+        ))?;
+        assert_eq!((response.status, response.body.as_str()), (200, "hello"));
+        assert_eq!(
+            super::Auto.send(&Request::get(format!("{url}/x")))?.status,
+            404
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains(&format!("User-Agent: {}\r\n", super::USER_AGENT)));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=a%20b%26c"));
+        assert!(requests[1].starts_with("GET /x HTTP/1.1\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn curl() {
+        let request = Request::get("https://adventofcode.com/2023/day/1/input")
+            .header("Cookie", "session=53cr3t")
+            .header("X-Quote", "a \"b\" \\c");
+        let (command, config) = super::Curl::command(&request);
+        assert!(command
+            .get_args()
+            .all(|arg| !arg.to_string_lossy().contains("53cr3t")));
+        assert!(config.contains("header = \"Cookie: session=53cr3t\"\n"));
+        assert!(config.contains("header = \"X-Quote: a \\\"b\\\" \\\\c\"\n"));
+    }
+
+    #[test]
+    fn chunked() -> crate::Result<()> {
+        let response = super::parse_response(
+            "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n",
+        )?;
+        assert_eq!(response.body, "hello world");
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+/// The integers from `start` included to `end` excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// The part shared with `other`, if there is any.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let i = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!i.is_empty()).then_some(i)
+    }
+
+    /// The parts before `at` and from `at` on, when they are not empty.
+    pub fn split(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let before = Interval::new(self.start, self.end.min(at));
+        let after = Interval::new(self.start.max(at), self.end);
+        (
+            (!before.is_empty()).then_some(before),
+            (!after.is_empty()).then_some(after),
+        )
+    }
+
+    /// The parts that are not in `other`: at most one before it and one after it.
+    /// An empty `other` removes nothing, so the whole interval is left in one piece.
+    pub fn subtract(&self, other: &Self) -> impl Iterator<Item = Self> {
+        let (before, after) = match other.is_empty() {
+            true => ((!self.is_empty()).then_some(*self), None),
+            false => (self.split(other.start).0, self.split(other.end).1),
+        };
+        before.into_iter().chain(after)
+    }
+}
+
+/// A set of integers stored as sorted intervals, which never overlap nor touch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { intervals: vec![] }
+    }
+}
+
+impl<T: Ord + Copy> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let mut merged = interval;
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        for &i in &self.intervals {
+            match i.end < merged.start || merged.end < i.start {
+                true => intervals.push(i),
+                false => merged = Interval::new(i.start.min(merged.start), i.end.max(merged.end)),
+            }
+        }
+        intervals.push(merged);
+        intervals.sort_unstable();
+        self.intervals = intervals;
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| i.subtract(&interval))
+            .collect();
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(x))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The smallest integer of the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    /// The intervals of the set, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Interval<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        iter.into_iter().for_each(|i| set.insert(i));
+        set
+    }
+}
+
+/// A map that moves each of its source intervals to another start, and leaves everything else where it is.
+/// When source intervals overlap, the first one added wins.
+/// A source is cut short so that its image, like every interval, ends by `u64::MAX`:
+/// the integers that would be moved to `u64::MAX` or past it are left where they are.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OffsetMap {
+    rules: Vec<(Interval<u64>, u64)>,
+}
+
+impl OffsetMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves `source` so that it starts at `destination`.
+    pub fn insert(&mut self, source: Interval<u64>, destination: u64) {
+        self.rules.push(rule(source, destination));
+    }
+
+    pub fn get(&self, x: u64) -> u64 {
+        self.rules
+            .iter()
+            .find(|(source, _)| source.contains(x))
+            .map_or(x, |&(source, destination)| destination + (x - source.start))
+    }
+
+    /// The image of every integer of `set`, computed from the interval endpoints only.
+    pub fn apply(&self, set: &RangeSet<u64>) -> RangeSet<u64> {
+        let mut unmoved = set.clone();
+        let mut moved = RangeSet::new();
+        for &(source, destination) in &self.rules {
+            let parts: Vec<_> = unmoved
+                .iter()
+                .filter_map(|i| i.intersect(&source))
+                .collect();
+            for part in parts {
+                moved.insert(Interval::new(
+                    destination + (part.start - source.start),
+                    destination + (part.end - source.start),
+                ));
+            }
+            unmoved.remove(source);
+        }
+        unmoved.iter().for_each(|&i| moved.insert(i));
+        moved
+    }
+}
+
+impl FromIterator<(Interval<u64>, u64)> for OffsetMap {
+    fn from_iter<I: IntoIterator<Item = (Interval<u64>, u64)>>(iter: I) -> Self {
+        OffsetMap {
+            rules: iter.into_iter().map(|(s, d)| rule(s, d)).collect(),
+        }
+    }
+}
+
+/// The rule moving `source` to `destination`, without the integers whose image would not end by `u64::MAX`.
+fn rule(source: Interval<u64>, destination: u64) -> (Interval<u64>, u64) {
+    let end = source
+        .end
+        .min(source.start.saturating_add(u64::MAX - destination));
+    (Interval::new(source.start, end), destination)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Interval, OffsetMap, RangeSet};
+
+    #[test]
+    fn interval() {
+        let i = Interval::new(2, 8);
+        assert_eq!(
+            i.intersect(&Interval::new(5, 10)),
+            Some(Interval::new(5, 8))
+        );
+        assert_eq!(i.intersect(&Interval::new(8, 10)), None);
+        assert_eq!(
+            i.split(5),
+            (Some(Interval::new(2, 5)), Some(Interval::new(5, 8)))
+        );
+        assert_eq!(i.split(2), (None, Some(i)));
+        assert_eq!(
+            i.subtract(&Interval::new(4, 6)).collect::<Vec<_>>(),
+            vec![Interval::new(2, 4), Interval::new(6, 8)]
+        );
+        assert_eq!(i.subtract(&Interval::new(0, 10)).count(), 0);
+        for empty in [Interval::new(5, 5), Interval::new(6, 4)] {
+            assert_eq!(i.subtract(&empty).collect::<Vec<_>>(), vec![i]);
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut set: RangeSet<i32> = [Interval::new(5, 7), Interval::new(0, 2)]
+            .into_iter()
+            .collect();
+        set.insert(Interval::new(2, 5));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![&Interval::new(0, 7)]);
+        set.remove(Interval::new(3, 4));
+        assert!(set.contains(2) && !set.contains(3) && set.contains(4));
+        assert_eq!(set.min(), Some(0));
+        set.remove(Interval::new(6, 1));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![&Interval::new(0, 3), &Interval::new(4, 7)]
+        );
+        set.remove(Interval::new(-1, 10));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn offset() {
+        let map: OffsetMap = [(Interval::new(98, 100), 50), (Interval::new(50, 98), 52)]
+            .into_iter()
+            .collect();
+        assert_eq!((map.get(79), map.get(99), map.get(10)), (81, 51, 10));
+        let set = map.apply(
+            &[Interval::new(45, 52), Interval::new(97, 100)]
+                .into_iter()
+                .collect(),
+        );
+        let expected: RangeSet<u64> = [45, 46, 47, 48, 49, 52, 53, 99, 50, 51]
+            .into_iter()
+            .map(|x| Interval::new(x, x + 1))
+            .collect();
+        assert_eq!(set, expected);
+
+        let mut map = OffsetMap::new();
+        map.insert(Interval::new(10, 20), u64::MAX - 5);
+        assert_eq!(
+            (map.get(14), map.get(15), map.get(19)),
+            (u64::MAX - 1, 15, 19)
+        );
+        let set = map.apply(&[Interval::new(0, 30)].into_iter().collect());
+        let expected: RangeSet<u64> = [
+            Interval::new(0, 10),
+            Interval::new(15, 30),
+            Interval::new(u64::MAX - 5, u64::MAX),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set, expected);
+    }
+}
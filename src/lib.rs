@@ -1,17 +1,163 @@
 use std::{
+    any::Any,
+    fmt::Display,
     fs::File,
-    io::{prelude::*, BufReader},
+    io::{self, prelude::*, BufReader},
     path::Path,
 };
 
-pub fn lines_from_file(filename: impl AsRef<Path>) -> Vec<String> {
-    let file = File::open(filename).expect("no such file");
-    let buf = BufReader::new(file);
-    buf.lines()
-        .map(|l| l.expect("Could not parse line"))
-        .collect()
+pub use answer::Answer;
+pub use diagnostic::Diagnostic;
+pub use error::{Error, Result};
+
+pub fn lines_from_file(filename: impl AsRef<Path>) -> Result<Vec<String>> {
+    let buf = BufReader::new(open(filename.as_ref())?);
+    Ok(buf.lines().collect::<io::Result<_>>()?)
+}
+
+/// Reads a whole input file, to be passed to [`Solution::parse`].
+pub fn input_from_file(filename: impl AsRef<Path>) -> Result<String> {
+    input_from_reader(BufReader::new(open(filename.as_ref())?))
+}
+
+/// Reads a whole input from a reader, such as the locked standard input.
+pub fn input_from_reader(mut reader: impl BufRead) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+fn open(filename: &Path) -> Result<File> {
+    File::open(filename).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::MissingFile(filename.into()),
+        _ => Error::Io(e),
+    })
+}
+
+/// A puzzle of the calendar: the input is parsed once and then shared by both parts.
+pub trait Solution {
+    /// Day of the calendar, starting from 1.
+    const DAY: u8;
+    /// Title of the puzzle, as found in the first line of the doc comment of `problem1`.
+    const TITLE: &'static str;
+
+    type Input;
+    type Output1: Display + Into<Answer>;
+    type Output2: Display + Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2>;
+
+    /// Checks the input without solving it. By default this is the error returned by [`Solution::parse`],
+    /// days that can find every problem of the input at once do so.
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        match Self::parse(input) {
+            Ok(_) => vec![],
+            Err(e) => vec![Diagnostic::from_error(e)],
+        }
+    }
+
+    /// Parses the input and solves the first part.
+    fn solve1(input: &str) -> Result<Self::Output1> {
+        Self::part1(&Self::parse(input)?)
+    }
+
+    /// Parses the input and solves the second part.
+    fn solve2(input: &str) -> Result<Self::Output2> {
+        Self::part2(&Self::parse(input)?)
+    }
+}
+
+/// Type-erased view of a [`Solution`], so that every day can be stored in [`DAYS`].
+pub trait Day: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>>;
+    /// The diagnostics of [`Solution::validate`], along with their offending lines.
+    fn validate(&self, input: &str) -> Vec<Diagnostic>;
+    /// Runs the given part (1 or 2) on an input returned by [`Day::parse`].
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<Answer>;
 }
 
+impl<S: Solution + Sync> Day for S
+where
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn validate(&self, input: &str) -> Vec<Diagnostic> {
+        S::validate(input)
+            .into_iter()
+            .map(|d| d.source(input))
+            .collect()
+    }
+
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("The input was parsed by another day");
+        match part {
+            1 => Ok(S::part1(input)?.into()),
+            2 => Ok(S::part2(input)?.into()),
+            _ => Err(Error::Invalid(format!("Day {} has no part {part}", S::DAY))),
+        }
+    }
+}
+
+/// Every day solved so far, in calendar order.
+pub static DAYS: [&dyn Day; 11] = [
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+];
+
+/// Looks up a day in [`DAYS`] by its number.
+pub fn day(n: u8) -> Option<&'static dyn Day> {
+    DAYS.iter().find(|d| d.day() == n).copied()
+}
+
+mod answer;
+mod error;
+
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod diagnostic;
+pub mod fetch;
+pub mod fuzz;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod http;
+pub mod interval;
+pub mod math;
+pub mod parse;
+pub mod progress;
+pub mod property;
+pub mod render;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
@@ -23,3 +169,41 @@ pub mod day08;
 pub mod day09;
 pub mod day10;
 pub mod day11;
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn registry() {
+        for (i, day) in super::DAYS.iter().enumerate() {
+            assert_eq!(day.day() as usize, i + 1);
+            assert!(!day.title().is_empty());
+        }
+        assert!(super::day(5).is_some_and(|d| d.title() == "If You Give A Seed A Fertilizer"));
+        assert!(super::day(25).is_none());
+    }
+
+    #[test]
+    fn examples() -> crate::Result<()> {
+        for day in super::DAYS {
+            // A day made by `aoc new` has an empty example until it is pasted in.
+            let path = format!("inputs/{:02}-example.txt", day.day());
+            if std::fs::metadata(&path).map_or(true, |m| m.len() == 0) {
+                continue;
+            }
+            let input = day.parse(&crate::input_from_file(path)?)?;
+            day.solve(input.as_ref(), 1)?;
+            assert!(day.solve(input.as_ref(), 3).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn from_str() -> crate::Result<()> {
+        use crate::Solution;
+
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+        assert_eq!(crate::input_from_reader(input.as_bytes())?, input);
+        assert_eq!(crate::day01::Day01::solve1(input)?, 142);
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+/// The greatest common divisor and lowest common multiple of unsigned integers.
+pub trait Divisors: Sized {
+    fn gcd(self, other: Self) -> Self;
+
+    /// `None` when the multiple does not fit in the type.
+    fn lcm(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! divisors {
+    ($($t:ty),*) => {$(
+        impl Divisors for $t {
+            fn gcd(mut self, mut other: Self) -> Self {
+                while other != 0 {
+                    (self, other) = (other, self % other);
+                }
+                self
+            }
+
+            fn lcm(self, other: Self) -> Option<Self> {
+                match self.gcd(other) {
+                    0 => Some(0),
+                    gcd => (self / gcd).checked_mul(other),
+                }
+            }
+        }
+    )*};
+}
+
+divisors!(u8, u16, u32, u64, u128, usize);
+
+pub fn gcd<T: Divisors>(a: T, b: T) -> T {
+    a.gcd(b)
+}
+
+/// `None` when the multiple does not fit in the type.
+pub fn lcm<T: Divisors>(a: T, b: T) -> Option<T> {
+    a.lcm(b)
+}
+
+/// The greatest common divisor `g` of `a` and `b`, along with `x` and `y` such that `a * x + b * y = g`.
+/// `None` when either is `i128::MIN`, whose absolute value does not fit in an `i128`.
+pub fn extended_gcd(a: i128, b: i128) -> Option<(i128, i128, i128)> {
+    if a == i128::MIN || b == i128::MIN {
+        return None;
+    }
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    Some(match r0 < 0 {
+        true => (-r0, -x0, -y0),
+        false => (r0, x0, y0),
+    })
+}
+
+/// The `x` in `0..m` such that `a * x` is 1 modulo `m`, if `a` and `m` are coprime and `m` is positive.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m)?;
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// The smallest non-negative `x` that is `r` modulo `m` for every `(r, m)`, along with the
+/// lowest common multiple of the moduli, which all the other solutions differ by.
+/// The moduli do not need to be coprime, but must be positive;
+/// `None` when one is not, when there is no solution, or when it does not fit in an `i128`.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    congruences.iter().try_fold((0, 1), |(r1, m1), &(r2, m2)| {
+        if m2 <= 0 {
+            return None;
+        }
+        let (g, p, _) = extended_gcd(m1, m2)?;
+        let diff = r2.rem_euclid(m2) - r1;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = m2 / g;
+        let t = (diff / g)
+            .rem_euclid(step)
+            .checked_mul(p.rem_euclid(step))?
+            % step;
+        let m = (m1 / g).checked_mul(m2)?;
+        Some((m1.checked_mul(t)?.checked_add(r1)?.rem_euclid(m), m))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn divisors() {
+        assert_eq!(super::gcd(12u32, 18), 6);
+        assert_eq!(super::gcd(0u64, 7), 7);
+        assert_eq!(super::lcm(4usize, 6), Some(12));
+        assert_eq!(super::lcm(0u8, 6), Some(0));
+        assert_eq!(super::lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(super::lcm(200u8, 100), Some(200));
+    }
+
+    #[test]
+    fn inverse() {
+        let (g, x, y) = super::extended_gcd(240, 46).unwrap();
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(super::extended_gcd(i128::MIN, 1), None);
+        assert_eq!(super::extended_gcd(1, i128::MIN), None);
+        assert_eq!(super::mod_inverse(3, 11), Some(4));
+        assert_eq!(super::mod_inverse(-3, 11), Some(7));
+        assert_eq!(super::mod_inverse(4, 12), None);
+        assert_eq!(super::mod_inverse(3, 0), None);
+        assert_eq!(super::mod_inverse(3, -11), None);
+        assert_eq!(super::mod_inverse(i128::MIN, 3), Some(1));
+    }
+
+    #[test]
+    fn crt() {
+        assert_eq!(super::crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(super::crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(super::crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(super::crt(&[]), Some((0, 1)));
+        assert_eq!(super::crt(&[(1, i128::MAX), (0, i128::MAX - 1)]), None);
+        assert_eq!(super::crt(&[(1, 4), (2, 0)]), None);
+        assert_eq!(super::crt(&[(1, -4), (2, 3)]), None);
+        assert_eq!(super::crt(&[(i128::MIN, 3)]), Some((1, 3)));
+        assert_eq!(super::crt(&[(i128::MAX, 2), (i128::MIN, 3)]), Some((1, 6)));
+    }
+}
@@ -0,0 +1,133 @@
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+/// A line of the input along with its position, starting from 0.
+/// Its helpers parse the shapes that keep coming back in the inputs. They work on slices of the line,
+/// so that the column of an error is the one of the offending token, however the line was split to get there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line<'a> {
+    pub index: usize,
+    pub text: &'a str,
+}
+
+/// The lines of the input.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line { index, text })
+}
+
+/// The groups of lines separated by blank lines, which are left out.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![vec![]];
+    for line in lines(input) {
+        match line.text.trim().is_empty() {
+            true => sections.push(vec![]),
+            false => sections.last_mut().unwrap().push(line),
+        }
+    }
+    sections.retain(|s| !s.is_empty());
+    sections
+}
+
+impl<'a> Line<'a> {
+    /// An error pointing at `token`, which must be a slice of the line.
+    pub fn error(&self, token: &str, message: impl Into<String>) -> Error {
+        Error::at(self.index, self.text, token, message)
+    }
+
+    /// Parses `token`, which must be a slice of the line, such as an integer.
+    pub fn number<T: FromStr>(&self, token: &'a str) -> Result<T> {
+        token
+            .parse()
+            .map_err(|_| self.error(token, "invalid number"))
+    }
+
+    /// Parses the whitespace-separated integers of `s`, which must be a slice of the line.
+    pub fn numbers<T: FromStr>(&self, s: &'a str) -> Result<Vec<T>> {
+        s.split_whitespace().map(|n| self.number(n)).collect()
+    }
+
+    /// Splits `s`, which must be a slice of the line, around the first `separator`.
+    /// The error says that `expected` was expected instead.
+    pub fn split_once(
+        &self,
+        s: &'a str,
+        separator: &str,
+        expected: &str,
+    ) -> Result<(&'a str, &'a str)> {
+        s.split_once(separator)
+            .ok_or_else(|| self.error(s, format!("expected '{expected}'")))
+    }
+
+    /// Splits a `<label>: <values>` line into its label and the values, with leading spaces trimmed.
+    pub fn labelled(&self) -> Result<(&'a str, &'a str)> {
+        let (label, values) = self.split_once(self.text, ":", "<label>: <values>")?;
+        Ok((label, values.trim_start()))
+    }
+
+    /// The values of a `<label>: <values>` line with the given label.
+    pub fn label(&self, label: &str) -> Result<&'a str> {
+        match self.text.split_once(':') {
+            Some((l, values)) if l == label => Ok(values.trim_start()),
+            _ => Err(self.error(self.text, format!("expected '{label}:'"))),
+        }
+    }
+
+    /// Splits a `KEY = (A, B)` line into its three names.
+    pub fn record(&self) -> Result<(&'a str, &'a str, &'a str)> {
+        let (key, rest) = self.split_once(self.text, " = ", "<key> = (<left>, <right>)")?;
+        let (left, right) = rest
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| s.split_once(", "))
+            .ok_or_else(|| self.error(rest, "expected '(<left>, <right>)'"))?;
+        Ok((key, left, right))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Error;
+
+    fn column<T: std::fmt::Debug>(result: crate::Result<T>) -> (usize, usize) {
+        match result {
+            Err(Error::Parse { line, column, .. }) => (line, column),
+            result => panic!("{result:?}"),
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        let line = super::lines("\nCard 1: 41 48 | 83 -86").nth(1).unwrap();
+        let (label, values) = line.labelled().unwrap();
+        assert_eq!(label, "Card 1");
+        let (winning, mine) = line.split_once(values, "|", "|").unwrap();
+        assert_eq!(line.numbers::<u32>(winning).unwrap(), vec![41, 48]);
+        assert_eq!(line.numbers::<i32>(mine).unwrap(), vec![83, -86]);
+        assert_eq!(column(line.numbers::<u32>(mine)), (2, 20));
+        assert_eq!(column(line.label("Game")), (2, 1));
+        assert_eq!(line.label("Card 1").unwrap(), "41 48 | 83 -86");
+    }
+
+    #[test]
+    fn sections() {
+        let sections = super::sections("a\nb\n\n\nc\n");
+        let texts: Vec<Vec<&str>> = sections
+            .iter()
+            .map(|s| s.iter().map(|l| l.text).collect())
+            .collect();
+        assert_eq!(texts, vec![vec!["a", "b"], vec!["c"]]);
+        assert_eq!(sections[1][0].index, 4);
+    }
+
+    #[test]
+    fn record() {
+        let line = super::lines("AAA = (BBB, CCC)\nAAA = BBB").next().unwrap();
+        assert_eq!(line.record().unwrap(), ("AAA", "BBB", "CCC"));
+        let line = super::lines("AAA = (BBB, CCC)\nAAA = BBB").nth(1).unwrap();
+        assert_eq!(column(line.record()), (2, 7));
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    cell::RefCell,
+    io::{IsTerminal, Write},
+    sync::{Arc, Mutex},
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<dyn Progress>>> = const { RefCell::new(None) };
+}
+
+/// Where solvers report how far along they are, and the diagnostics that do not stop them,
+/// instead of printing them along with the answers.
+pub trait Progress: Send + Sync {
+    /// `done` out of `total` units of work are done.
+    fn progress(&self, done: u64, total: u64);
+    fn log(&self, message: &str);
+}
+
+/// Runs `f` with `progress` installed on the current thread, so that [`report`] and [`log`] use it.
+/// Without one, they do nothing.
+pub fn scope<T>(progress: Arc<dyn Progress>, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(progress)));
+    // Restore the previous one even if `f` panics.
+    struct Restore(Option<Arc<dyn Progress>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(previous);
+    f()
+}
+
+pub fn report(done: u64, total: u64) {
+    CURRENT.with(|current| {
+        if let Some(progress) = &*current.borrow() {
+            progress.progress(done, total);
+        }
+    });
+}
+
+pub fn log(message: impl AsRef<str>) {
+    CURRENT.with(|current| {
+        if let Some(progress) = &*current.borrow() {
+            progress.log(message.as_ref());
+        }
+    });
+}
+
+/// Keeps everything that was reported, to be inspected by tests.
+#[derive(Debug, Default)]
+pub struct Capture {
+    pub progress: Mutex<Vec<(u64, u64)>>,
+    pub messages: Mutex<Vec<String>>,
+}
+
+impl Progress for Capture {
+    fn progress(&self, done: u64, total: u64) {
+        self.progress.lock().unwrap().push((done, total));
+    }
+
+    fn log(&self, message: &str) {
+        self.messages.lock().unwrap().push(message.to_string());
+    }
+}
+
+/// Draws a progress bar on the standard error, prefixed by `label`, and prints the messages above it.
+/// When the standard error is not a terminal, only the messages are printed.
+pub struct Bar {
+    label: String,
+    drawn: Mutex<bool>,
+    terminal: bool,
+}
+
+impl Bar {
+    const WIDTH: u64 = 40;
+
+    pub fn new(label: impl Into<String>) -> Self {
+        Bar {
+            label: label.into(),
+            drawn: Mutex::new(false),
+            terminal: std::io::stderr().is_terminal(),
+        }
+    }
+
+    /// The bar once `done` of `total` is done, computed in `u128` so that any `total` fits.
+    fn line(&self, done: u64, total: u64) -> String {
+        let (done, total) = (u128::from(done.min(total)), u128::from(total));
+        let filled = (done * u128::from(Bar::WIDTH) / total) as usize;
+        format!(
+            "{} [{}{}] {:>3}%",
+            self.label,
+            "#".repeat(filled),
+            " ".repeat(Bar::WIDTH as usize - filled),
+            done * 100 / total
+        )
+    }
+
+    /// Erases the bar, if it was drawn.
+    pub fn clear(&self) {
+        let mut drawn = self.drawn.lock().unwrap();
+        if *drawn {
+            eprint!("\r\x1b[2K");
+            *drawn = false;
+        }
+    }
+}
+
+impl Progress for Bar {
+    fn progress(&self, done: u64, total: u64) {
+        if !self.terminal || total == 0 {
+            return;
+        }
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r{}", self.line(done, total));
+        let _ = stderr.flush();
+        *self.drawn.lock().unwrap() = true;
+    }
+
+    fn log(&self, message: &str) {
+        self.clear();
+        eprintln!("{}: {message}", self.label);
+    }
+}
+
+impl Drop for Bar {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{Bar, Capture};
+
+    #[test]
+    fn scope() {
+        // Nothing is installed: this goes nowhere.
+        super::log("lost");
+
+        let capture = Arc::new(Capture::default());
+        super::scope(capture.clone(), || {
+            super::report(1, 4);
+            super::log("skipped");
+            super::scope(Arc::new(Capture::default()), || super::log("elsewhere"));
+            super::report(4, 4);
+        });
+        super::report(5, 4);
+
+        assert_eq!(*capture.progress.lock().unwrap(), vec![(1, 4), (4, 4)]);
+        assert_eq!(*capture.messages.lock().unwrap(), vec!["skipped"]);
+    }
+
+    #[test]
+    fn bar() {
+        let bar = Bar::new("day 5");
+        assert_eq!(
+            bar.line(1, 4),
+            format!("day 5 [{}{}]  25%", "#".repeat(10), " ".repeat(30))
+        );
+        assert_eq!(
+            bar.line(u64::MAX / 2, u64::MAX),
+            format!("day 5 [{}{}]  49%", "#".repeat(19), " ".repeat(21))
+        );
+        assert_eq!(
+            bar.line(u64::MAX, u64::MAX),
+            format!("day 5 [{}] 100%", "#".repeat(40))
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use std::{collections::HashSet, fmt::Debug};
+
+use crate::{geometry::Direction, grid::Grid, Error, Result};
+
+/// How many cases [`check`] runs, unless `PROPERTY_CASES` says otherwise.
+const CASES: u64 = 200;
+
+/// The seed of the first case, unless `PROPERTY_SEED` says otherwise.
+const SEED: u64 = 20231225;
+
+/// A small deterministic random number generator (SplitMix64), so that every failure can be replayed from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, which must not be empty.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn between(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    /// True once every `n` times on average.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn env(name: &str) -> Option<u64> {
+    std::env::var(name).ok()?.parse().ok()
+}
+
+/// Checks that `property` holds for values made by `generate`, each case from its own seed.
+///
+/// On the first failure, panics with the seed and the value; running again with `PROPERTY_SEED=<seed>`
+/// starts from that case. `PROPERTY_CASES` changes the number of cases.
+pub fn check<T: Debug>(generate: impl Fn(&mut Rng) -> T, property: impl Fn(&T) -> Result<()>) {
+    let first = env("PROPERTY_SEED").unwrap_or(SEED);
+    for seed in first..first + env("PROPERTY_CASES").unwrap_or(CASES) {
+        let value = generate(&mut Rng::new(seed));
+        if let Err(e) = property(&value) {
+            panic!("property failed with PROPERTY_SEED={seed}: {e}\nvalue: {value:#?}");
+        }
+    }
+}
+
+/// Fails with `message` unless `condition` holds.
+pub fn ensure(condition: bool, message: impl FnOnce() -> String) -> Result<()> {
+    match condition {
+        true => Ok(()),
+        false => Err(Error::Invalid(message())),
+    }
+}
+
+/// A random input that is valid for the day, shaped like the actual puzzle inputs,
+/// or `None` when the day has no generator yet.
+pub fn input(day: u8, rng: &mut Rng) -> Option<String> {
+    Some(match day {
+        1 => calibration(rng),
+        2 => games(rng),
+        3 => schematic(rng),
+        4 => scratchcards(rng),
+        5 => almanac(rng),
+        6 => races(rng),
+        7 => hands(rng),
+        8 => network(rng),
+        9 => sequences(rng),
+        10 => pipes(rng),
+        11 => universe(rng),
+        _ => return None,
+    })
+}
+
+fn lines(rng: &mut Rng, max: usize, line: impl Fn(&mut Rng, usize) -> String) -> String {
+    let n = rng.between(1, max as i64) as usize;
+    (0..n).map(|i| line(rng, i) + "\n").collect()
+}
+
+fn calibration(rng: &mut Rng) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    lines(rng, 20, |rng, _| {
+        let mut line = rng.below(10).to_string();
+        for _ in 0..rng.below(6) {
+            let token = match rng.below(3) {
+                0 => rng.below(10).to_string(),
+                1 => rng.pick(&WORDS).to_string(),
+                _ => ((b'a' + rng.below(26) as u8) as char).to_string(),
+            };
+            match rng.one_in(2) {
+                true => line.push_str(&token),
+                false => line.insert_str(0, &token),
+            }
+        }
+        line
+    })
+}
+
+fn games(rng: &mut Rng) -> String {
+    lines(rng, 20, |rng, i| {
+        let reveals: Vec<String> = (0..rng.between(1, 5))
+            .map(|_| {
+                let mut colours = ["red", "green", "blue"];
+                rng.shuffle(&mut colours);
+                colours[..rng.between(1, 3) as usize]
+                    .iter()
+                    .map(|colour| format!("{} {colour}", rng.between(1, 20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        format!("Game {}: {}", i + 1, reveals.join("; "))
+    })
+}
+
+fn schematic(rng: &mut Rng) -> String {
+    let width = rng.between(1, 15) as usize;
+    lines(rng, 15, |rng, _| {
+        let mut row = String::new();
+        while row.len() < width {
+            match rng.below(4) {
+                0 => row += &format!("{}.", rng.between(0, 999)),
+                1 => row.push(*rng.pick(&['*', '#', '+', '$', '/', '@', '=', '%', '-', '&'])),
+                _ => row.push('.'),
+            }
+        }
+        row.truncate(width);
+        row
+    })
+}
+
+fn scratchcards(rng: &mut Rng) -> String {
+    lines(rng, 20, |rng, i| {
+        let mut numbers: Vec<i64> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, mine) = numbers.split_at(5);
+        let mut mine = mine[..8].to_vec();
+        // At most five matches, so that the copies stay far from overflowing.
+        for n in mine.iter_mut().take(rng.below(6)) {
+            *n = *rng.pick(winning);
+        }
+        let join = |ns: &[i64]| ns.iter().map(|n| format!("{n:2}")).collect::<Vec<_>>();
+        format!(
+            "Card {:3}: {} | {}",
+            i + 1,
+            join(winning).join(" "),
+            join(&mine).join(" ")
+        )
+    })
+}
+
+fn almanac(rng: &mut Rng) -> String {
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+    let seeds: Vec<String> = (0..rng.between(1, 4))
+        .map(|_| format!("{} {}", rng.between(0, 999), rng.between(1, 100)))
+        .collect();
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+    for map in MAPS {
+        almanac += &format!("\n{map} map:\n");
+        for _ in 0..rng.between(1, 5) {
+            almanac += &format!(
+                "{} {} {}\n",
+                rng.between(0, 999),
+                rng.between(0, 999),
+                rng.between(1, 200)
+            );
+        }
+    }
+    almanac
+}
+
+fn races(rng: &mut Rng) -> String {
+    let times: Vec<i64> = (0..rng.between(1, 4)).map(|_| rng.between(1, 99)).collect();
+    let distances: Vec<i64> = times.iter().map(|&t| rng.between(0, t * t / 4)).collect();
+    let row = |ns: &[i64]| ns.iter().map(|n| format!("{n:>6}")).collect::<String>();
+    format!("Time:    {}\nDistance:{}\n", row(&times), row(&distances))
+}
+
+fn hands(rng: &mut Rng) -> String {
+    lines(rng, 50, |rng, _| {
+        let cards: String = (0..5)
+            .map(|_| *rng.pick(&"23456789TJQKA".chars().collect::<Vec<_>>()))
+            .collect();
+        format!("{cards} {}", rng.between(1, 1000))
+    })
+}
+
+/// Chains of nodes that go from a node ending with A to one ending with Z whatever the instructions,
+/// along with a few nodes that cannot be reached.
+fn network(rng: &mut Rng) -> String {
+    let mut names = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut name = |rng: &mut Rng, last: &[char]| loop {
+        let letter = |rng: &mut Rng| (b'A' + rng.below(26) as u8) as char;
+        let name: String = [letter(rng), letter(rng), *rng.pick(last)].iter().collect();
+        if names.insert(name.clone()) {
+            return name;
+        }
+    };
+    let middle: Vec<char> = ('B'..='Y').collect();
+
+    let mut lines = vec![];
+    for chain in 0..rng.between(1, 4) {
+        let mut nodes = vec![match chain {
+            0 => "AAA".to_string(),
+            _ => name(rng, &['A']),
+        }];
+        for _ in 0..rng.below(8) {
+            nodes.push(name(rng, &middle));
+        }
+        nodes.push(match chain {
+            0 => "ZZZ".to_string(),
+            _ => name(rng, &['Z']),
+        });
+        let last = nodes.len() - 1;
+        for (i, node) in nodes.iter().enumerate() {
+            let mut next = || match i {
+                _ if i == last => &nodes[1],
+                _ => &nodes[(i + 1 + rng.below(2)).min(last)],
+            };
+            lines.push(format!("{node} = ({}, {})", next(), next()));
+        }
+    }
+    let reachable: Vec<String> = lines.iter().map(|l| l[..3].to_string()).collect();
+    for _ in 0..rng.below(4) {
+        let node = name(rng, &middle);
+        lines.push(format!(
+            "{node} = ({}, {})",
+            rng.pick(&reachable),
+            rng.pick(&reachable)
+        ));
+    }
+    rng.shuffle(&mut lines);
+
+    let instructions: String = (0..rng.between(1, 10))
+        .map(|_| *rng.pick(&['L', 'R']))
+        .collect();
+    format!("{instructions}\n\n{}\n", lines.join("\n"))
+}
+
+/// Values of polynomials, so that the differences end up all zero.
+fn sequences(rng: &mut Rng) -> String {
+    lines(rng, 10, |rng, _| {
+        let degree = rng.below(4);
+        let coefficients: Vec<i64> = (0..=degree).map(|_| rng.between(-5, 5)).collect();
+        let start = rng.between(-5, 5);
+        (start..start + rng.between(degree as i64 + 2, 15))
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |value, c| value * x + c)
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+/// A loop shaped like a histogram, turned a random number of times, with junk pipes around it.
+fn pipes(rng: &mut Rng) -> String {
+    let columns = rng.between(2, 10) as usize;
+    let bottom = rng.between(1, 10) as usize;
+    let mut tops: Vec<usize> = (0..columns).map(|_| rng.below(bottom)).collect();
+    tops[columns - 1] = tops[columns - 2];
+
+    // The tiles of the loop, in order: up the first column, along the tops, down the last column and back.
+    let mut tiles: Vec<(usize, usize)> = (tops[0]..=bottom).rev().map(|i| (i, 0)).collect();
+    for j in 1..columns {
+        let (from, to) = (tops[j - 1], tops[j]);
+        match from <= to {
+            true => tiles.extend((from..=to).map(|i| (i, j))),
+            false => tiles.extend((to..=from).rev().map(|i| (i, j))),
+        }
+    }
+    tiles.extend((tops[columns - 1] + 1..=bottom).map(|i| (i, columns - 1)));
+    tiles.extend((1..columns - 1).rev().map(|j| (bottom, j)));
+
+    let margin = (rng.below(3), rng.below(3));
+    let (width, height) = (
+        columns + margin.1 + rng.below(3),
+        bottom + 1 + margin.0 + rng.below(3),
+    );
+    let junk: Vec<char> = "|-LJ7F..".chars().collect();
+    let cells = (0..width * height).map(|_| *rng.pick(&junk)).collect();
+    let mut grid = Grid::new(width, cells);
+
+    let tiles: Vec<_> = tiles
+        .iter()
+        .map(|&(i, j)| (i + margin.0, j + margin.1))
+        .collect();
+    let start = rng.below(tiles.len());
+    for (k, &at) in tiles.iter().enumerate() {
+        let towards = |other: (usize, usize)| {
+            Direction::ALL
+                .into_iter()
+                .find(|&d| grid.step(at, d) == Some(other))
+                .unwrap()
+        };
+        let before = towards(tiles[(k + tiles.len() - 1) % tiles.len()]);
+        let after = towards(tiles[(k + 1) % tiles.len()]);
+        grid[at] = match (before, after) {
+            _ if k == start => 'S',
+            (Direction::Up, Direction::Down) | (Direction::Down, Direction::Up) => '|',
+            (Direction::Left, Direction::Right) | (Direction::Right, Direction::Left) => '-',
+            (Direction::Up, Direction::Right) | (Direction::Right, Direction::Up) => 'L',
+            (Direction::Up, Direction::Left) | (Direction::Left, Direction::Up) => 'J',
+            (Direction::Down, Direction::Left) | (Direction::Left, Direction::Down) => '7',
+            _ => 'F',
+        };
+    }
+    // Nothing but the loop may connect to S.
+    let start = tiles[start];
+    for d in Direction::ALL {
+        if let Some(at) = grid.step(start, d).filter(|at| !tiles.contains(at)) {
+            grid[at] = '.';
+        }
+    }
+
+    // Turning the map turns the pipes too.
+    for _ in 0..rng.below(4) {
+        let rotated = grid.rotate();
+        let cells = rotated.iter().map(|(_, &tile)| match tile {
+            '|' => '-',
+            '-' => '|',
+            'L' => 'F',
+            'F' => '7',
+            '7' => 'J',
+            'J' => 'L',
+            c => c,
+        });
+        grid = Grid::new(rotated.width(), cells.collect());
+    }
+    grid.rows()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+fn universe(rng: &mut Rng) -> String {
+    let width = rng.between(1, 15) as usize;
+    lines(rng, 15, |rng, _| {
+        (0..width)
+            .map(|_| match rng.one_in(5) {
+                true => '#',
+                false => '.',
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rng;
+    use crate::DAYS;
+
+    #[test]
+    fn rng() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert!((0..1000).all(|_| (-3..=3).contains(&a.between(-3, 3))));
+        let mut items: Vec<_> = (0..10).collect();
+        a.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    /// Every generated input is valid for its day, and both parts solve it.
+    #[test]
+    fn valid() {
+        // Days without a generator yet, such as one just made by `aoc new`, are skipped.
+        for day in DAYS {
+            if super::input(day.day(), &mut super::Rng::new(0)).is_none() {
+                continue;
+            }
+            super::check(
+                |rng| super::input(day.day(), rng).unwrap(),
+                |input| {
+                    crate::diagnostic::check(day, input)?;
+                    let parsed = day.parse(input)?;
+                    day.solve(parsed.as_ref(), 1)?;
+                    day.solve(parsed.as_ref(), 2)?;
+                    Ok(())
+                },
+            );
+        }
+    }
+}
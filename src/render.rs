@@ -0,0 +1,135 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    io::IsTerminal,
+};
+
+use crate::grid::{Coord, Grid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    /// The ANSI escape code of the foreground colour.
+    fn code(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+/// Whether colours should be used: only when the standard output is a terminal, and `NO_COLOR` is not set.
+pub fn colours() -> bool {
+    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Draws a grid one row per line, with some of its cells highlighted in colour.
+///
+/// Without colours, the cells are drawn as they are, except those highlighted with [`Render::mark`].
+/// When a cell is highlighted more than once, the last highlight wins.
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    styles: HashMap<Coord, (Colour, Option<char>)>,
+    colours: bool,
+}
+
+impl<'a, T> Render<'a, T> {
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Render {
+            grid,
+            styles: HashMap::new(),
+            colours: colours(),
+        }
+    }
+
+    /// Turns colours on or off, instead of deciding from the standard output.
+    pub fn colours(mut self, colours: bool) -> Self {
+        self.colours = colours;
+        self
+    }
+
+    pub fn highlight(mut self, cells: impl IntoIterator<Item = Coord>, colour: Colour) -> Self {
+        self.styles
+            .extend(cells.into_iter().map(|at| (at, (colour, None))));
+        self
+    }
+
+    /// Like [`Render::highlight`], but without colours the cells are drawn as `mark`.
+    pub fn mark(
+        mut self,
+        cells: impl IntoIterator<Item = Coord>,
+        colour: Colour,
+        mark: char,
+    ) -> Self {
+        self.styles
+            .extend(cells.into_iter().map(|at| (at, (colour, Some(mark)))));
+        self
+    }
+
+    pub fn rows(self, rows: impl IntoIterator<Item = usize>, colour: Colour) -> Self {
+        let width = self.grid.width();
+        let cells: Vec<_> = rows
+            .into_iter()
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .collect();
+        self.highlight(cells, colour)
+    }
+
+    pub fn columns(self, columns: impl IntoIterator<Item = usize>, colour: Colour) -> Self {
+        let height = self.grid.height();
+        let cells: Vec<_> = columns
+            .into_iter()
+            .flat_map(|j| (0..height).map(move |i| (i, j)))
+            .collect();
+        self.highlight(cells, colour)
+    }
+}
+
+impl<T: Display> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (at, cell) in self.grid.iter() {
+            match (self.styles.get(&at), self.colours) {
+                (Some((colour, _)), true) => write!(f, "\x1b[{}m{cell}\x1b[0m", colour.code())?,
+                (Some((_, Some(mark))), false) => write!(f, "{mark}")?,
+                _ => write!(f, "{cell}")?,
+            }
+            if at.1 + 1 == self.grid.width() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Colour, Render};
+    use crate::grid::Grid;
+
+    #[test]
+    fn render() {
+        let grid = Grid::parse("ab\ncd", "invalid", Some).unwrap();
+        let render = || {
+            Render::new(&grid)
+                .rows([1], Colour::Blue)
+                .mark([(0, 1), (1, 1)], Colour::Red, '*')
+        };
+        assert_eq!(render().colours(false).to_string(), "a*\nc*\n");
+        assert_eq!(
+            render().colours(true).to_string(),
+            "a\x1b[31mb\x1b[0m\n\x1b[34mc\x1b[0m\x1b[31md\x1b[0m\n"
+        );
+    }
+}
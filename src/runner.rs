@@ -0,0 +1,282 @@
+use std::{
+    any::Any,
+    fmt::Write,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{mpsc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    bench::format_duration, cancel::Token, diagnostic, input_from_file, Answer, Day, Error, Result,
+};
+
+/// How long a job may run past its budget to notice that it was cancelled and report it.
+const GRACE: Duration = Duration::from_millis(100);
+
+/// One part of a day to solve, on the input read from `input`.
+pub struct Job {
+    pub day: &'static dyn Day,
+    pub part: u8,
+    pub input: PathBuf,
+}
+
+/// The outcome of a [`Job`]: `time` includes reading and parsing the input.
+#[derive(Debug)]
+pub struct Run {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<Answer>,
+    pub time: Duration,
+}
+
+impl Job {
+    /// Both parts of every given day, on their inputs in `inputs/NN.txt`.
+    pub fn all(days: &[&'static dyn Day]) -> Vec<Job> {
+        days.iter()
+            .flat_map(|&day| {
+                [1, 2].map(|part| Job {
+                    day,
+                    part,
+                    input: format!("inputs/{:02}.txt", day.day()).into(),
+                })
+            })
+            .collect()
+    }
+
+    fn run(&self, token: &Token) -> Run {
+        let start = Instant::now();
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+            token.scope(|| {
+                let input = input_from_file(&self.input)?;
+                diagnostic::check(self.day, &input)?;
+                let input = self.day.parse(&input)?;
+                self.day.solve(input.as_ref(), self.part)
+            })
+        }))
+        .unwrap_or_else(|payload| Err(Error::Panicked(panic_message(payload))));
+        Run {
+            day: self.day.day(),
+            part: self.part,
+            answer,
+            time: start.elapsed(),
+        }
+    }
+
+    /// Runs the job on its own thread, and gives up on it once the budget is spent.
+    /// A solver that does not check its [`Token`] keeps running in the background until it returns,
+    /// but its answer is discarded.
+    fn run_within(self, budget: Option<Duration>) -> Run {
+        let Some(budget) = budget else {
+            return self.run(&Token::new());
+        };
+        let token = Token::with_budget(budget);
+        let (day, part) = (self.day.day(), self.part);
+        let (sender, receiver) = mpsc::channel();
+        let child = token.clone();
+        thread::spawn(move || {
+            let _ = sender.send(self.run(&child));
+        });
+
+        receiver.recv_timeout(budget + GRACE).unwrap_or_else(|_| {
+            token.cancel();
+            Run {
+                day,
+                part,
+                answer: Err(Error::TimedOut(budget)),
+                time: budget,
+            }
+        })
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".into(),
+        },
+    }
+}
+
+/// The number of threads used when none is given: one for each available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Runs the jobs on a pool of `threads` threads, each taking the next job as soon as it is done
+/// with the previous one, so that a slow job only holds up its own thread.
+/// A panicking job is reported as [`Error::Panicked`], and a job still running once its `budget`
+/// is spent as [`Error::TimedOut`]. The runs are sorted by day and part.
+pub fn run(jobs: Vec<Job>, threads: usize, budget: Option<Duration>) -> Vec<Run> {
+    let threads = threads.clamp(1, jobs.len().max(1));
+    let queue = Mutex::new(jobs.into_iter());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                // The lock is released before running the job.
+                let job = queue.lock().unwrap().next();
+                match job {
+                    Some(job) => sender.send(job.run_within(budget)).unwrap(),
+                    None => break,
+                }
+            });
+        }
+    });
+    drop(sender);
+
+    let mut runs: Vec<Run> = receiver.into_iter().collect();
+    runs.sort_by_key(|run| (run.day, run.part));
+    runs
+}
+
+/// Formats the runs as one line each, followed by a summary.
+pub fn report(runs: &[Run], elapsed: Duration) -> String {
+    let mut out = String::new();
+    for run in runs {
+        let _ = match &run.answer {
+            Ok(answer) => writeln!(
+                out,
+                "Day {:02} part {}: {answer} ({})",
+                run.day,
+                run.part,
+                format_duration(run.time)
+            ),
+            Err(e) => writeln!(
+                out,
+                "Day {:02} part {}: FAILED after {}: {e}",
+                run.day,
+                run.part,
+                format_duration(run.time)
+            ),
+        };
+    }
+    let failed = runs.iter().filter(|run| run.answer.is_err()).count();
+    let _ = writeln!(
+        out,
+        "{} solved, {failed} failed in {}",
+        runs.len() - failed,
+        format_duration(elapsed)
+    );
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{Result, Solution};
+
+    struct Panics;
+
+    impl Solution for Panics {
+        const DAY: u8 = 25;
+        const TITLE: &'static str = "Panics";
+
+        type Input = ();
+        type Output1 = u8;
+        type Output2 = u8;
+
+        fn parse(_input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Result<u8> {
+            panic!("part 1 of day 25")
+        }
+
+        fn part2(_input: &()) -> Result<u8> {
+            Ok(50)
+        }
+    }
+
+    /// Part 1 gives up when its token is cancelled, part 2 never does.
+    struct Slow;
+
+    impl Solution for Slow {
+        const DAY: u8 = 24;
+        const TITLE: &'static str = "Slow";
+
+        type Input = ();
+        type Output1 = u8;
+        type Output2 = u8;
+
+        fn parse(_input: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Result<u8> {
+            loop {
+                crate::cancel::check()?;
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        }
+
+        fn part2(_input: &()) -> Result<u8> {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn run() {
+        let mut jobs = super::Job::all(&[&Panics, crate::DAYS[5], crate::DAYS[0]]);
+        for job in &mut jobs {
+            job.input = match job.day.day() {
+                25 => "inputs/01-example1.txt".into(),
+                1 => format!("inputs/01-example{}.txt", job.part).into(),
+                _ => "inputs/missing.txt".into(),
+            };
+        }
+
+        let runs = super::run(jobs, 4, None);
+        let answers: Vec<_> = runs
+            .iter()
+            .map(|run| (run.day, run.part, run.answer.as_ref().ok().cloned()))
+            .collect();
+        assert_eq!(
+            answers,
+            vec![
+                (1, 1, Some(142u32.into())),
+                (1, 2, Some(281u32.into())),
+                (6, 1, None),
+                (6, 2, None),
+                (25, 1, None),
+                (25, 2, Some(50u8.into())),
+            ]
+        );
+        assert!(
+            matches!(&runs[4].answer, Err(crate::Error::Panicked(m)) if m == "part 1 of day 25")
+        );
+
+        let report = super::report(&runs, Duration::from_millis(3));
+        assert!(report.contains("Day 06 part 1: FAILED after "));
+        assert!(report.ends_with("3 solved, 3 failed in 3.00ms\n"));
+    }
+
+    #[test]
+    fn budget() {
+        let mut jobs = super::Job::all(&[&Slow, crate::DAYS[0]]);
+        for job in &mut jobs {
+            job.input = "inputs/01-example1.txt".into();
+        }
+
+        let start = std::time::Instant::now();
+        let runs = super::run(jobs, 1, Some(Duration::from_millis(50)));
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(runs[0].answer.as_ref().ok(), Some(&142u32.into()));
+        for run in &runs[2..] {
+            assert!(matches!(run.answer, Err(crate::Error::TimedOut(_))));
+        }
+        assert_eq!(
+            runs[3].answer.as_ref().unwrap_err().to_string(),
+            "timed out after 0.05 s"
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Result};
+
+/// The module of a new day, where `%DAY%` is its number, `%NN%` its number on two digits
+/// and `%TITLE%` the title of the puzzle.
+const TEMPLATE: &str = r#"use crate::{Error, Result, Solution};
+
+/// Day %DAY%: %TITLE%
+///
+/// TODO: the description of the first part of the puzzle.
+pub fn problem1(input: Vec<String>) -> Result<u64> {
+    Day%NN%::part1(&Day%NN%::parse(&input.join("\n"))?)
+}
+
+/// TODO: the description of the second part of the puzzle.
+pub fn problem2(input: Vec<String>) -> Result<u64> {
+    Day%NN%::part2(&Day%NN%::parse(&input.join("\n"))?)
+}
+
+pub struct Day%NN%;
+
+impl Solution for Day%NN% {
+    const DAY: u8 = %DAY%;
+    const TITLE: &'static str = %TITLE_STR%;
+
+    type Input = Vec<String>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(_input: &str) -> Result<Self::Input> {
+        Err(Error::Invalid("Day %DAY% is not parsed yet".into()))
+    }
+
+    fn part1(_input: &Self::Input) -> Result<u64> {
+        Err(Error::Invalid("Day %DAY% part 1 is not solved yet".into()))
+    }
+
+    fn part2(_input: &Self::Input) -> Result<u64> {
+        Err(Error::Invalid("Day %DAY% part 2 is not solved yet".into()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn problem1() -> crate::Result<()> {
+        // Once part 1 is solved, check its answer on the example instead.
+        assert!(super::problem1(crate::lines_from_file("inputs/%NN%-example.txt")?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn problem2() -> crate::Result<()> {
+        // Once part 2 is solved, check its answer on the example instead.
+        assert!(super::problem2(crate::lines_from_file("inputs/%NN%-example.txt")?).is_err());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod solution {
+    #[test]
+    #[ignore = "part 1 is not solved yet"]
+    fn problem1() -> crate::Result<()> {
+        let solution = super::problem1(crate::lines_from_file("inputs/%NN%.txt")?)?;
+        println!("Solution for day %NN% problem 1: {}", solution);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "part 2 is not solved yet"]
+    fn problem2() -> crate::Result<()> {
+        let solution = super::problem2(crate::lines_from_file("inputs/%NN%.txt")?)?;
+        println!("Solution for day %NN% problem 2: {}", solution);
+        Ok(())
+    }
+}
+"#;
+
+/// Fills the template of the module of a day.
+pub fn module(day: u8, title: &str) -> String {
+    TEMPLATE
+        .replace("%TITLE_STR%", &format!("{title:?}"))
+        .replace("%TITLE%", title)
+        .replace("%NN%", &format!("{day:02}"))
+        .replace("%DAY%", &day.to_string())
+}
+
+/// Creates `src/dayNN.rs`, `inputs/NN.txt` and `inputs/NN-example.txt` under `root`,
+/// then declares the module in `src/lib.rs` and adds the day to [`crate::DAYS`].
+/// Returns the files that were created or changed.
+pub fn create(root: impl AsRef<Path>, day: u8, title: &str) -> Result<Vec<PathBuf>> {
+    let root = root.as_ref();
+    if !(1..=25).contains(&day) {
+        return Err(Error::Invalid(format!("There is no day {day}")));
+    }
+    let module_path = root.join(format!("src/day{day:02}.rs"));
+    if module_path.exists() {
+        return Err(Error::Invalid(format!(
+            "{} already exists",
+            module_path.display()
+        )));
+    }
+    let lib_path = root.join("src/lib.rs");
+    let lib = register(&fs::read_to_string(&lib_path)?, day)?;
+
+    fs::write(&module_path, module(day, title))?;
+    let mut changed = vec![module_path];
+    fs::create_dir_all(root.join("inputs"))?;
+    // Only the example: the input itself is left for `fetch` to download.
+    let example_path = root.join(format!("inputs/{day:02}-example.txt"));
+    if !example_path.exists() {
+        fs::write(&example_path, "")?;
+        changed.push(example_path);
+    }
+    fs::write(&lib_path, lib)?;
+    changed.push(lib_path);
+    Ok(changed)
+}
+
+/// Adds `pub mod dayNN;` and `&dayNN::DayNN` to the source of `lib.rs`, keeping the days in order.
+fn register(lib: &str, day: u8) -> Result<String> {
+    let module = format!("day{day:02}");
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    let missing = |what: &str| Error::Invalid(format!("Could not find {what} in lib.rs"));
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub static DAYS: [&dyn Day; "))
+        .ok_or_else(|| missing("DAYS"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l == "];")
+            .ok_or_else(|| missing("the end of DAYS"))?;
+    let at = (start + 1..end)
+        .find(|&i| lines[i].trim() > format!("&{module}::").as_str())
+        .unwrap_or(end);
+    lines.insert(at, format!("    &{module}::Day{day:02},"));
+    lines[start] = format!("pub static DAYS: [&dyn Day; {}] = [", end - start);
+
+    let days: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod day"))
+        .collect();
+    let last = *days.last().ok_or_else(|| missing("the days modules"))?;
+    let at = days
+        .into_iter()
+        .find(|&i| lines[i].as_str() > format!("pub mod {module};").as_str())
+        .unwrap_or(last + 1);
+    lines.insert(at, format!("pub mod {module};"));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod test {
+    /// The parts of `lib.rs` that [`super::register`] changes.
+    const LIB: &str = "pub mod bench;
+pub mod day01;
+pub mod day11;
+
+pub static DAYS: [&dyn Day; 2] = [
+    &day01::Day01,
+    &day11::Day11,
+];
+";
+
+    #[test]
+    fn register() -> crate::Result<()> {
+        let lib = super::register(LIB, 12)?;
+        assert!(lib.contains("pub mod day11;\npub mod day12;\n"));
+        assert!(lib.contains(
+            "[&dyn Day; 3] = [\n    &day01::Day01,\n    &day11::Day11,\n    &day12::Day12,\n];"
+        ));
+        let lib = super::register(&lib, 5)?;
+        assert!(lib.contains("pub mod bench;\npub mod day01;\npub mod day05;\npub mod day11;\n"));
+        assert!(lib.contains("[&dyn Day; 4] = [\n    &day01::Day01,\n    &day05::Day05,\n"));
+        assert!(super::register("pub mod bench;\n", 12).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn create() -> crate::Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src"))?;
+        std::fs::write(root.join("src/lib.rs"), LIB)?;
+
+        let changed = super::create(&root, 12, "Hot Springs")?;
+        assert_eq!(changed.len(), 3);
+        let module = std::fs::read_to_string(root.join("src/day12.rs"))?;
+        assert!(module
+            .starts_with("use crate::{Error, Result, Solution};\n\n/// Day 12: Hot Springs\n"));
+        assert!(module.contains("const TITLE: &'static str = \"Hot Springs\";"));
+        assert!(module.contains("crate::lines_from_file(\"inputs/12-example.txt\")"));
+        assert!(root.join("inputs/12-example.txt").exists());
+        assert!(!root.join("inputs/12.txt").exists());
+        assert!(std::fs::read_to_string(root.join("src/lib.rs"))?.contains("&day12::Day12,"));
+
+        assert!(super::create(&root, 12, "Hot Springs").is_err());
+        std::fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    /// `aoc new` followed by `aoc fetch` downloads the input of the new day.
+    #[test]
+    fn fetch() -> crate::Result<()> {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-fetch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src"))?;
+        std::fs::write(root.join("src/lib.rs"), LIB)?;
+
+        super::create(&root, 12, "Hot Springs")?;
+        let (url, server) = crate::http::stub::serve(vec![(200, "???.### 1,1,3\n")]);
+        let path = crate::fetch::Fetcher::new(crate::http::TcpHttp, Some("cookie".into()))
+            .base_url(&url)
+            .dir(root.join("inputs"))
+            .fetch(12)?;
+        assert_eq!(std::fs::read_to_string(path)?, "???.### 1,1,3\n");
+        assert!(server.join().unwrap()[0].starts_with("GET /day/12/input HTTP/1.1\r\n"));
+
+        std::fs::remove_dir_all(root)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    fetch::BASE_URL,
+    http::{Http, Request},
+    Answer, Error, Result,
+};
+
+/// The file where every submission and its verdict is recorded, as tab-separated values.
+pub const LOG_FILE: &str = "submissions.tsv";
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, but the server did not say whether it is too high or too low.
+    Wrong,
+    /// An answer was submitted too recently: the number of seconds to wait before the next one.
+    Wait(u64),
+    /// The part has already been solved, or it is not unlocked yet.
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    /// Reads the verdict from the page returned by the server after a submission.
+    pub fn parse(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Verdict::Correct
+        } else if page.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::Wait(wait_time(page).unwrap_or(60))
+        } else if page.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether submitting the same answer again would give the same verdict.
+    /// [`Verdict::AlreadySolved`] is not: a part that was not unlocked yet may be by then.
+    fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            Verdict::Wait(_) | Verdict::AlreadySolved | Verdict::Unknown
+        )
+    }
+}
+
+/// Parses "You have 4m 27s left to wait".
+fn wait_time(page: &str) -> Option<u64> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    page[start..end].split_whitespace().try_fold(0, |secs, t| {
+        let (n, unit) = t.split_at(t.find(|c: char| !c.is_ascii_digit())?);
+        let n: u64 = n.parse().ok()?;
+        match unit {
+            "h" => Some(secs + n * 3600),
+            "m" => Some(secs + n * 60),
+            "s" => Some(secs + n),
+            _ => None,
+        }
+    })
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(secs) => write!(f, "wait {secs} seconds"),
+            Verdict::AlreadySolved => write!(f, "already solved"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, ()> {
+        Ok(match s {
+            "correct" => Verdict::Correct,
+            "too high" => Verdict::TooHigh,
+            "too low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            "already solved" => Verdict::AlreadySolved,
+            "unknown" => Verdict::Unknown,
+            s => Verdict::Wait(
+                s.strip_prefix("wait ")
+                    .and_then(|s| s.strip_suffix(" seconds"))
+                    .and_then(|s| s.parse().ok())
+                    .ok_or(())?,
+            ),
+        })
+    }
+}
+
+/// Posts answers, and records each verdict in [`LOG_FILE`].
+pub struct Submitter<H> {
+    http: H,
+    session: String,
+    base_url: String,
+    log: PathBuf,
+}
+
+impl<H: Http> Submitter<H> {
+    pub fn new(http: H, session: String) -> Self {
+        Submitter {
+            http,
+            session,
+            base_url: BASE_URL.into(),
+            log: LOG_FILE.into(),
+        }
+    }
+
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = url.into();
+        self
+    }
+
+    pub fn log(mut self, path: impl AsRef<Path>) -> Self {
+        self.log = path.as_ref().into();
+        self
+    }
+
+    /// The verdict recorded for this answer, if it was already submitted.
+    pub fn recorded(&self, day: u8, part: u8, answer: &Answer) -> Result<Option<Verdict>> {
+        let answer = answer.to_string();
+        let log = match fs::read_to_string(&self.log) {
+            Ok(log) => log,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(log
+            .lines()
+            .rev()
+            .find_map(|line| match line.split('\t').collect::<Vec<_>>()[..] {
+                [d, p, a, verdict]
+                    if d == day.to_string() && p == part.to_string() && a == answer =>
+                {
+                    verdict.parse().ok()
+                }
+                _ => None,
+            }))
+    }
+
+    /// Submits the answer, unless the same answer has already been judged.
+    pub fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<Verdict> {
+        let text = answer.to_string();
+        if text.is_empty() || text.contains(['\t', '\n']) {
+            return Err(Error::Invalid(format!("Invalid answer: {text:?}")));
+        }
+        if let Some(verdict) = self.recorded(day, part, answer)?.filter(Verdict::is_final) {
+            return Ok(verdict);
+        }
+
+        let request = Request::post(
+            format!("{}/day/{day}/answer", self.base_url),
+            &[("level", &part.to_string()), ("answer", &text)],
+        )
+        .header("Cookie", &format!("session={}", self.session));
+        let response = self.http.send(&request)?;
+        if response.status != 200 {
+            return Err(Error::Http {
+                status: response.status,
+                message: response.body.trim().to_string(),
+            });
+        }
+
+        let verdict = Verdict::parse(&response.body);
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log)?;
+        writeln!(log, "{day}\t{part}\t{text}\t{verdict}")?;
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Verdict;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Verdict::parse("<article><p>That's the right answer!  You are one gold star closer"),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(
+                "That's not the right answer; your answer is too high.  If you're stuck"
+            ),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer.  If you're stuck, make sure"),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 27s left to wait."),
+            Verdict::Wait(267)
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(Verdict::parse("<html></html>"), Verdict::Unknown);
+
+        for verdict in [Verdict::Wait(30), Verdict::TooLow, Verdict::AlreadySolved] {
+            assert_eq!(verdict.to_string().parse(), Ok(verdict));
+        }
+    }
+
+    #[test]
+    fn submit() -> crate::Result<()> {
+        let log = std::env::temp_dir().join(format!("aoc-submit-{}.tsv", std::process::id()));
+        let _ = std::fs::remove_file(&log);
+        let (url, server) = crate::http::stub::serve(vec![
+            (
+                200,
+                "You gave an answer too recently.  You have 32s left to wait.",
+            ),
+            (200, "That's not the right answer; your answer is too low."),
+        ]);
+        let submitter = super::Submitter::new(crate::http::TcpHttp, "cookie".into())
+            .base_url(&url)
+            .log(&log);
+
+        let answer = 41u32.into();
+        assert_eq!(submitter.submit(1, 2, &answer)?, Verdict::Wait(32));
+        assert_eq!(submitter.submit(1, 2, &answer)?, Verdict::TooLow);
+        // The verdict is final, so it is not submitted again.
+        assert_eq!(submitter.submit(1, 2, &"41".into())?, Verdict::TooLow);
+        assert_eq!(submitter.recorded(1, 1, &answer)?, None);
+
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("POST /day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[1].contains("Cookie: session=cookie\r\n"));
+        assert!(requests[1].ends_with("\r\n\r\nlevel=2&answer=41"));
+        assert_eq!(
+            std::fs::read_to_string(&log)?,
+            "1\t2\t41\twait 32 seconds\n1\t2\t41\ttoo low\n"
+        );
+
+        std::fs::remove_file(log)?;
+        Ok(())
+    }
+
+    /// A part that is not unlocked yet gets the same answer as a solved one,
+    /// so the answer is submitted again once it is.
+    #[test]
+    fn locked() -> crate::Result<()> {
+        let log = std::env::temp_dir().join(format!("aoc-locked-{}.tsv", std::process::id()));
+        let _ = std::fs::remove_file(&log);
+        let (url, server) = crate::http::stub::serve(vec![
+            (
+                200,
+                "You don't seem to be solving the right level.  Did you already complete it?",
+            ),
+            (200, "That's the right answer!"),
+        ]);
+        let submitter = super::Submitter::new(crate::http::TcpHttp, "cookie".into())
+            .base_url(&url)
+            .log(&log);
+
+        let answer = 41u32.into();
+        assert_eq!(submitter.submit(3, 2, &answer)?, Verdict::AlreadySolved);
+        assert_eq!(submitter.submit(3, 2, &answer)?, Verdict::Correct);
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        std::fs::remove_file(log)?;
+        Ok(())
+    }
+}
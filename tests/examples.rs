@@ -0,0 +1,109 @@
+use std::process::ExitCode;
+
+use adventofcode2023::answers::{self, Outcome};
+
+/// The libtest options whose value comes as the next argument, which is then not the filter.
+const OPTIONS_WITH_VALUE: [&str; 6] = [
+    "--skip",
+    "--test-threads",
+    "--format",
+    "--color",
+    "--logfile",
+    "--shuffle-seed",
+];
+
+/// The first argument that is neither an option nor the value of one.
+fn filter(args: &[String]) -> Option<&String> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if OPTIONS_WITH_VALUE.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+/// Checks every example of `answers.toml`, reporting each one like a test of its own,
+/// and fails for the example inputs that are not in it.
+///
+/// It runs with `cargo test`, and takes the same filter as the other tests:
+/// `cargo test --test examples 08` only checks the examples of day 8.
+/// The examples that are not in `answers.toml` are only reported when there is no filter.
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let filter = filter(&args);
+    let records = match answers::load("answers.toml") {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("answers.toml: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let unlisted = match answers::unlisted_examples(&records, "inputs") {
+        Ok(unlisted) => unlisted,
+        Err(e) => {
+            eprintln!("inputs: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let examples: Vec<_> = records
+        .iter()
+        .filter(|record| record.is_example())
+        .filter(|record| match &filter {
+            Some(f) => record.name().contains(f.as_str()),
+            None => true,
+        })
+        .collect();
+
+    if args.iter().any(|arg| arg == "--list") {
+        for record in &examples {
+            println!("{}: test", record.name());
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    println!("\nrunning {} examples", examples.len());
+    let mut passed = 0;
+    let mut failures = vec![];
+    for record in examples.iter().copied() {
+        let failure = match answers::check(record) {
+            Outcome::Correct => None,
+            Outcome::Wrong(answer) => Some(format!("expected {}, got {answer}", record.expected)),
+            Outcome::Failed(e) => Some(e.to_string()),
+        };
+        println!(
+            "example {} ... {}",
+            record.name(),
+            if failure.is_some() { "FAILED" } else { "ok" }
+        );
+        match failure {
+            Some(failure) => failures.push(format!("{}: {failure}", record.name())),
+            None => passed += 1,
+        }
+    }
+    if filter.is_none() {
+        for path in unlisted {
+            println!("example {} ... FAILED", path.display());
+            failures.push(format!("{}: not in answers.toml", path.display()));
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for failure in &failures {
+            println!("    {failure}");
+        }
+    }
+    println!(
+        "\nexample result: {}. {} passed; {} failed\n",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        passed,
+        failures.len()
+    );
+    match failures.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}